@@ -1,7 +1,6 @@
 uniffi::setup_scaffolding!("praborrow");
 
-use praborrow_core::{Sovereign, SovereignState};
-use std::sync::Arc;
+use praborrow_core::Sovereign;
 
 #[derive(Debug, thiserror::Error, uniffi::Error)]
 pub enum BindingError {
@@ -1,9 +1,300 @@
+pub mod cluster;
+
+use crate::frb_generated::StreamSink;
 use flutter_rust_bridge::frb;
-use praborrow_core::{Sovereign, SovereignState, RepatriationToken};
+use praborrow_core::{RepatriationToken, Sovereign};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Errors surfaced to Dart. Each variant is generated as a distinct exception type.
+#[derive(Debug, thiserror::Error)]
+pub enum DartSovereignError {
+    #[error("Resource is exiled")]
+    Exiled,
+    #[error("Invalid hex identifier: {0}")]
+    InvalidId(String),
+    #[error("Resource is already leased")]
+    AlreadyLeased,
+    #[error("Lease rejected: {0}")]
+    LeaseRejected(String),
+    #[error("Annexation error: {0}")]
+    Annexation(String),
+    #[error("Repatriation token was not issued for this resource: {0}")]
+    ForgedToken(String),
+    #[error("Repatriation token has already been used")]
+    TokenAlreadyUsed,
+    #[error("Malformed JSON at byte {offset}: {message}")]
+    InvalidJson { offset: u64, message: String },
+    #[error("Invalid invariant rule {name}: {reason}")]
+    InvalidRule { name: String, reason: String },
+    #[error("Invariants violated: {}", failed.join(", "))]
+    InvariantViolation { failed: Vec<String> },
+    #[error("Proof backend {0} is not available in this build")]
+    SolverUnavailable(String),
+    #[error("Verification cancelled")]
+    Cancelled,
+}
+
+/// Snapshot of an active lease. Identifiers are 128-bit values rendered as hex strings.
+pub struct DartLease {
+    pub lease_id: String,
+    pub holder: String,
+    pub epoch: u64,
+    pub remaining_ms: u64,
+}
+
+/// Lease granted through this wrapper. Core reports only the id and epoch, so the grant
+/// instant is kept here to count down the remaining time.
+struct LeaseRecord {
+    lease_id: u128,
+    holder: u128,
+    epoch: u64,
+    granted_at: Instant,
+    duration: Duration,
+}
+
+impl LeaseRecord {
+    fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.granted_at.elapsed())
+    }
+
+    fn to_dart(&self) -> DartLease {
+        DartLease {
+            lease_id: format!("{:x}", self.lease_id),
+            holder: format!("{:x}", self.holder),
+            epoch: self.epoch,
+            remaining_ms: self.remaining().as_millis() as u64,
+        }
+    }
+}
+
+/// Coarse jurisdiction of a sovereign as seen from Dart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DartJurisdiction {
+    Domestic,
+    Leased,
+    /// The lease has run out but the resource has not been reclaimed yet.
+    Lapsed,
+    Exiled,
+}
+
+/// One jurisdiction transition delivered to a `watch_state` stream.
+pub struct SovereignStateDto {
+    pub state: DartJurisdiction,
+    pub holder: Option<String>,
+    pub remaining_ms: Option<u64>,
+    /// Transitions skipped since the previous event because the listener fell behind.
+    pub missed: u64,
+}
+
+/// What happened in a `DartJurisdictionEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DartJurisdictionEventKind {
+    Annexed,
+    Repatriated,
+    Leased,
+    Lapsed,
+    Reclaimed,
+}
+
+/// One entry of `history()`.
+#[derive(Debug, Clone)]
+pub struct DartJurisdictionEvent {
+    pub kind: DartJurisdictionEventKind,
+    pub timestamp_ms: u64,
+    /// Epoch of the most recent lease core granted, 0 before the first one.
+    pub epoch: u64,
+}
+
+/// Number of events `history()` keeps; older ones are dropped first.
+const HISTORY_LIMIT: usize = 64;
+
+#[derive(Clone)]
+struct StateSnapshot {
+    state: DartJurisdiction,
+    holder: Option<u128>,
+    deadline: Option<Instant>,
+}
+
+impl StateSnapshot {
+    const DOMESTIC: Self = Self {
+        state: DartJurisdiction::Domestic,
+        holder: None,
+        deadline: None,
+    };
+}
+
+struct StateSlot {
+    /// Bumped on every transition, so a watcher can tell how many it skipped.
+    seq: u64,
+    latest: StateSnapshot,
+    closed: bool,
+    epoch: u64,
+    history: VecDeque<DartJurisdictionEvent>,
+}
+
+/// Latest jurisdiction of a sovereign, shared with the threads that feed `watch_state`
+/// streams and time out leases. Only the newest state is kept, so a slow listener never
+/// holds up the sovereign.
+struct StateCell {
+    slot: Mutex<StateSlot>,
+    changed: Condvar,
+}
+
+impl StateCell {
+    fn new() -> Self {
+        Self {
+            slot: Mutex::new(StateSlot {
+                seq: 0,
+                latest: StateSnapshot::DOMESTIC,
+                closed: false,
+                epoch: 0,
+                history: VecDeque::new(),
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StateSlot> {
+        self.slot.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Publishes `snapshot`, logs it as `kind` and returns its sequence number.
+    fn publish(&self, kind: DartJurisdictionEventKind, snapshot: StateSnapshot) -> u64 {
+        self.publish_at_epoch(kind, snapshot, None)
+    }
+
+    fn publish_at_epoch(
+        &self,
+        kind: DartJurisdictionEventKind,
+        snapshot: StateSnapshot,
+        epoch: Option<u64>,
+    ) -> u64 {
+        let mut slot = self.lock();
+        if let Some(epoch) = epoch {
+            slot.epoch = epoch;
+        }
+        if slot.history.len() == HISTORY_LIMIT {
+            slot.history.pop_front();
+        }
+        let event = DartJurisdictionEvent {
+            kind,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_millis() as u64),
+            epoch: slot.epoch,
+        };
+        slot.history.push_back(event);
+        slot.seq += 1;
+        slot.latest = snapshot;
+        self.changed.notify_all();
+        slot.seq
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.changed.notify_all();
+    }
+
+    /// Marks the lease published as `seq` lapsed once `deadline` passes, unless another
+    /// transition or the sovereign's drop comes first.
+    fn expire_at(&self, seq: u64, deadline: Instant) {
+        let mut slot = self.lock();
+        while slot.seq == seq && !slot.closed {
+            let now = Instant::now();
+            if now >= deadline {
+                let lapsed = StateSnapshot {
+                    state: DartJurisdiction::Lapsed,
+                    ..slot.latest.clone()
+                };
+                drop(slot);
+                self.publish(DartJurisdictionEventKind::Lapsed, lapsed);
+                return;
+            }
+            slot = self
+                .changed
+                .wait_timeout(slot, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Feeds `emit` the current state and then every later one until the cell closes or
+    /// `emit` returns `false`.
+    fn forward(&self, mut emit: impl FnMut(SovereignStateDto) -> bool) {
+        let mut seen = None;
+        loop {
+            let (seq, snapshot) = {
+                let slot = self
+                    .changed
+                    .wait_while(self.lock(), |slot| Some(slot.seq) == seen && !slot.closed)
+                    .unwrap_or_else(PoisonError::into_inner);
+                if Some(slot.seq) == seen {
+                    return;
+                }
+                (slot.seq, slot.latest.clone())
+            };
+            let missed = seen.map_or(0, |seen| seq - seen - 1);
+            seen = Some(seq);
+            let dto = SovereignStateDto {
+                state: snapshot.state,
+                holder: snapshot.holder.map(|holder| format!("{:x}", holder)),
+                remaining_ms: snapshot.deadline.map(|deadline| {
+                    deadline
+                        .saturating_duration_since(Instant::now())
+                        .as_millis() as u64
+                }),
+                missed,
+            };
+            if !emit(dto) {
+                return;
+            }
+        }
+    }
+}
+
+/// Source of repatriation token ids, unique across every sovereign in the process.
+static NEXT_TOKEN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Token returned by `annex()`; hand it back to `repatriate()` to restore the resource.
+///
+/// A sovereign only accepts the token from its latest annexation, and only once.
+#[frb(opaque)]
+pub struct DartRepatriationToken {
+    id: u64,
+    /// Peer the resource was annexed to. Core identifies a repatriation by this holder.
+    holder: u128,
+    used: AtomicBool,
+}
+
+impl DartRepatriationToken {
+    #[frb(sync)]
+    pub fn to_hex(&self) -> String {
+        format!("{:016x}", self.id)
+    }
+
+    #[frb(sync)]
+    pub fn holder(&self) -> String {
+        format!("{:x}", self.holder)
+    }
+}
 
 #[frb(opaque)]
 pub struct DartSovereignString {
     inner: Sovereign<String>,
+    /// Id of the token handed out by the last `annex()`, until it is redeemed.
+    issued: Mutex<Option<u64>>,
+    /// Lease from the last `grant_lease()`, until it is reclaimed.
+    lease: Mutex<Option<LeaseRecord>>,
+    state: Arc<StateCell>,
+}
+
+impl Drop for DartSovereignString {
+    fn drop(&mut self) {
+        self.state.close();
+    }
 }
 
 impl DartSovereignString {
@@ -11,6 +302,9 @@ impl DartSovereignString {
     pub fn new(value: String) -> Self {
         Self {
             inner: Sovereign::new(value),
+            issued: Mutex::new(None),
+            lease: Mutex::new(None),
+            state: Arc::new(StateCell::new()),
         }
     }
 
@@ -30,7 +324,831 @@ impl DartSovereignString {
         }
     }
 
-    pub fn annex(&self) -> anyhow::Result<()> {
-        self.inner.annex().map_err(|e| anyhow::anyhow!("Annex error: {:?}", e))
+    #[frb(sync)]
+    pub fn set_value(&mut self, value: String) -> Result<(), DartSovereignError> {
+        let slot = self
+            .inner
+            .try_get_mut()
+            .map_err(|_| DartSovereignError::Exiled)?;
+        *slot = value;
+        Ok(())
+    }
+
+    /// Exiles the resource to `holder_id_hex` and returns the token that brings it back.
+    pub fn annex(
+        &self,
+        holder_id_hex: String,
+    ) -> Result<DartRepatriationToken, DartSovereignError> {
+        let holder = parse_hex_id(&holder_id_hex)?;
+        self.inner
+            .annex()
+            .map_err(|e| DartSovereignError::Annexation(format!("{:?}", e)))?;
+        let id = NEXT_TOKEN_ID.fetch_add(1, Ordering::Relaxed);
+        *self.issued.lock().unwrap_or_else(PoisonError::into_inner) = Some(id);
+        self.state.publish(
+            DartJurisdictionEventKind::Annexed,
+            StateSnapshot {
+                state: DartJurisdiction::Exiled,
+                holder: Some(holder),
+                deadline: None,
+            },
+        );
+        Ok(DartRepatriationToken {
+            id,
+            holder,
+            used: AtomicBool::new(false),
+        })
+    }
+
+    /// Restores an annexed resource. A rejected token is left unspent.
+    pub fn repatriate(&self, token: &DartRepatriationToken) -> Result<(), DartSovereignError> {
+        if token.used.load(Ordering::Acquire) {
+            return Err(DartSovereignError::TokenAlreadyUsed);
+        }
+        let mut issued = self.issued.lock().unwrap_or_else(PoisonError::into_inner);
+        if *issued != Some(token.id) {
+            return Err(DartSovereignError::ForgedToken(token.to_hex()));
+        }
+        if token.used.swap(true, Ordering::AcqRel) {
+            return Err(DartSovereignError::TokenAlreadyUsed);
+        }
+        *issued = None;
+
+        // SAFETY: the token was issued by this sovereign's own annex() for `token.holder`
+        // and is spent here, so the resource cannot be repatriated twice.
+        self.inner
+            .repatriate(unsafe { RepatriationToken::new(token.holder) });
+        self.state.publish(
+            DartJurisdictionEventKind::Repatriated,
+            StateSnapshot::DOMESTIC,
+        );
+        Ok(())
+    }
+
+    /// Leases the resource to `peer_id_hex`. A lease that is still running fails with
+    /// `AlreadyLeased`; any other refusal from core is passed through as `LeaseRejected`.
+    #[frb(sync)]
+    pub fn grant_lease(
+        &self,
+        peer_id_hex: String,
+        duration_ms: u64,
+    ) -> Result<DartLease, DartSovereignError> {
+        let peer = parse_hex_id(&peer_id_hex)?;
+        let mut lease = self.lease.lock().unwrap_or_else(PoisonError::into_inner);
+        if lease.as_ref().is_some_and(|l| !l.remaining().is_zero()) {
+            return Err(DartSovereignError::AlreadyLeased);
+        }
+
+        let duration = Duration::from_millis(duration_ms);
+        let granted_at = Instant::now();
+        let (lease_id, epoch) = self
+            .inner
+            .grant_lease(peer, duration)
+            .map_err(DartSovereignError::LeaseRejected)?;
+        let record = LeaseRecord {
+            lease_id: lease_id.0,
+            holder: peer,
+            epoch: epoch.0,
+            granted_at,
+            duration,
+        };
+        let granted = record.to_dart();
+        let deadline = granted_at + duration;
+        *lease = Some(record);
+
+        let seq = self.state.publish_at_epoch(
+            DartJurisdictionEventKind::Leased,
+            StateSnapshot {
+                state: DartJurisdiction::Leased,
+                holder: Some(peer),
+                deadline: Some(deadline),
+            },
+            Some(epoch.0),
+        );
+        let state = Arc::clone(&self.state);
+        std::thread::spawn(move || state.expire_at(seq, deadline));
+        Ok(granted)
+    }
+
+    /// The lease from the last `grant_lease()`. It stays visible with `remaining_ms` at 0
+    /// after it lapses, until `reclaim()` takes the resource back.
+    #[frb(sync)]
+    pub fn lease_info(&self) -> Option<DartLease> {
+        self.lease
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(LeaseRecord::to_dart)
+    }
+
+    /// Takes back a lapsed lease. Core ignores the call while the lease is running.
+    #[frb(sync)]
+    pub fn reclaim(&self) {
+        self.inner.reclaim();
+        if !self.inner.is_exiled()
+            && self
+                .lease
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take()
+                .is_some()
+        {
+            self.state.publish(
+                DartJurisdictionEventKind::Reclaimed,
+                StateSnapshot::DOMESTIC,
+            );
+        }
+    }
+
+    /// The most recent jurisdiction changes made through this wrapper, oldest first,
+    /// capped at 64 entries.
+    #[frb(sync)]
+    pub fn history(&self) -> Vec<DartJurisdictionEvent> {
+        self.state.lock().history.iter().cloned().collect()
+    }
+
+    /// Streams the current state followed by every transition: annexation,
+    /// repatriation, a granted lease, its lapse and its reclaim.
+    ///
+    /// A slow Dart listener only sees the newest state; the transitions it skipped are
+    /// counted in `missed`. The stream completes once the sovereign is dropped.
+    pub fn watch_state(&self, sink: StreamSink<SovereignStateDto>) {
+        let state = Arc::clone(&self.state);
+        std::thread::spawn(move || state.forward(|dto| sink.add(dto).is_ok()));
+    }
+}
+
+fn parse_hex_id(raw: &str) -> Result<u128, DartSovereignError> {
+    let digits = raw.trim().trim_start_matches("0x");
+    u128::from_str_radix(digits, 16).map_err(|_| DartSovereignError::InvalidId(raw.to_string()))
+}
+
+/// Comparison operator of a numeric JSON invariant.
+#[derive(Debug, Clone, Copy)]
+enum JsonComparison {
+    Ge,
+    Le,
+    Gt,
+    Lt,
+    Eq,
+    Ne,
+}
+
+impl JsonComparison {
+    fn holds(self, actual: f64, expected: f64) -> bool {
+        match self {
+            Self::Ge => actual >= expected,
+            Self::Le => actual <= expected,
+            Self::Gt => actual > expected,
+            Self::Lt => actual < expected,
+            Self::Eq => actual == expected,
+            Self::Ne => actual != expected,
+        }
+    }
+}
+
+/// A parsed invariant over a JSON document.
+///
+/// Rules are either `required <path>` or `<path> <op> <number>` where `op` is one of
+/// `>=`, `<=`, `>`, `<`, `==`, `!=`. Paths are dot-separated (`account.balance`,
+/// `items.0.qty`).
+#[derive(Debug, Clone)]
+enum JsonRule {
+    Required(String),
+    Compare {
+        pointer: String,
+        op: JsonComparison,
+        bound: f64,
+    },
+}
+
+impl JsonRule {
+    fn parse(rule: &str) -> Result<Self, String> {
+        let rule = rule.trim();
+        if let Some(path) = rule.strip_prefix("required ") {
+            return Ok(Self::Required(json_pointer(path.trim())?));
+        }
+
+        const OPERATORS: [(&str, JsonComparison); 6] = [
+            (">=", JsonComparison::Ge),
+            ("<=", JsonComparison::Le),
+            ("==", JsonComparison::Eq),
+            ("!=", JsonComparison::Ne),
+            (">", JsonComparison::Gt),
+            ("<", JsonComparison::Lt),
+        ];
+        for (token, op) in OPERATORS {
+            if let Some((path, bound)) = rule.split_once(token) {
+                let bound = bound
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| format!("`{}` is not a number", bound.trim()))?;
+                return Ok(Self::Compare {
+                    pointer: json_pointer(path.trim())?,
+                    op,
+                    bound,
+                });
+            }
+        }
+        Err("expected `required <path>` or `<path> <op> <number>`".to_string())
+    }
+
+    fn holds(&self, value: &serde_json::Value) -> bool {
+        match self {
+            Self::Required(pointer) => value.pointer(pointer).is_some_and(|v| !v.is_null()),
+            Self::Compare { pointer, op, bound } => value
+                .pointer(pointer)
+                .and_then(serde_json::Value::as_f64)
+                .is_some_and(|actual| op.holds(actual, *bound)),
+        }
+    }
+}
+
+fn json_pointer(path: &str) -> Result<String, String> {
+    if path.is_empty() || path.split('.').any(str::is_empty) {
+        return Err(format!("invalid path `{}`", path));
+    }
+    Ok(path
+        .split('.')
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect())
+}
+
+fn parse_json(raw: &str) -> Result<serde_json::Value, DartSovereignError> {
+    serde_json::from_str(raw).map_err(|e| DartSovereignError::InvalidJson {
+        offset: byte_offset(raw, e.line(), e.column()),
+        message: e.to_string(),
+    })
+}
+
+/// Converts serde_json's 1-based line/column position into a byte offset.
+fn byte_offset(raw: &str, line: usize, column: usize) -> u64 {
+    let line_start: usize = raw
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    (line_start + column.saturating_sub(1)).min(raw.len()) as u64
+}
+
+/// Backend used by `verify_integrity`.
+pub enum DartProofBackend {
+    /// Evaluates every registered invariant against the current document. This is the
+    /// same runtime check `update_json` applies, not a proof.
+    Empirical,
+    /// SMT-based proof. `praborrow-prover` is not linked into the Dart bindings, so this
+    /// always fails with `SolverUnavailable`.
+    Smt,
+}
+
+/// Outcome of an integrity check. A refuted document is reported here, not as an error.
+pub struct ProofResultDto {
+    pub verified: bool,
+    pub checked: u32,
+    pub failed: Vec<String>,
+    pub elapsed_ms: u64,
+}
+
+/// A sovereign JSON document whose updates are validated against named invariants.
+#[frb(opaque)]
+pub struct DartSovereignJson {
+    inner: Sovereign<serde_json::Value>,
+    invariants: Vec<(String, JsonRule)>,
+}
+
+impl DartSovereignJson {
+    #[frb(sync)]
+    pub fn new(json: String) -> Result<Self, DartSovereignError> {
+        Ok(Self {
+            inner: Sovereign::new(parse_json(&json)?),
+            invariants: Vec::new(),
+        })
+    }
+
+    #[frb(sync)]
+    pub fn is_exiled(&self) -> bool {
+        self.inner.is_exiled()
+    }
+
+    #[frb(sync)]
+    pub fn get_json(&self) -> Result<String, DartSovereignError> {
+        self.inner
+            .try_get()
+            .map(|value| value.to_string())
+            .map_err(|_| DartSovereignError::Exiled)
+    }
+
+    /// Registers a named invariant. Existing rules with the same name are replaced.
+    #[frb(sync)]
+    pub fn add_invariant(&mut self, name: String, rule: String) -> Result<(), DartSovereignError> {
+        let parsed = JsonRule::parse(&rule).map_err(|reason| DartSovereignError::InvalidRule {
+            name: name.clone(),
+            reason,
+        })?;
+        self.invariants.retain(|(existing, _)| *existing != name);
+        self.invariants.push((name, parsed));
+        Ok(())
+    }
+
+    /// Replaces the document, rejecting it if any registered invariant fails.
+    #[frb(sync)]
+    pub fn update_json(&mut self, json: String) -> Result<(), DartSovereignError> {
+        let candidate = parse_json(&json)?;
+        let failed: Vec<String> = self
+            .invariants
+            .iter()
+            .filter(|(_, rule)| !rule.holds(&candidate))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if !failed.is_empty() {
+            return Err(DartSovereignError::InvariantViolation { failed });
+        }
+
+        let slot = self
+            .inner
+            .try_get_mut()
+            .map_err(|_| DartSovereignError::Exiled)?;
+        *slot = candidate;
+        Ok(())
+    }
+
+    /// Re-checks the current document against every registered invariant.
+    ///
+    /// Runs on flutter_rust_bridge's worker pool, so a large rule set does not stall the
+    /// Dart isolate; `verify_integrity_with_progress` also reports how far it got.
+    pub fn verify_integrity(&self) -> Result<ProofResultDto, DartSovereignError> {
+        self.verify_integrity_with_backend(DartProofBackend::Empirical)
+    }
+
+    pub fn verify_integrity_with_backend(
+        &self,
+        backend: DartProofBackend,
+    ) -> Result<ProofResultDto, DartSovereignError> {
+        self.run_verification(backend, |_| true)
+    }
+
+    /// Same check as `verify_integrity`, reporting progress in `0.0..=1.0`.
+    ///
+    /// Cancelling the Dart stream subscription aborts the check at the next rule.
+    pub fn verify_integrity_with_progress(
+        &self,
+        sink: StreamSink<f64>,
+    ) -> Result<ProofResultDto, DartSovereignError> {
+        self.run_verification(DartProofBackend::Empirical, |progress| {
+            sink.add(progress).is_ok()
+        })
+    }
+
+    fn run_verification(
+        &self,
+        backend: DartProofBackend,
+        mut on_progress: impl FnMut(f64) -> bool,
+    ) -> Result<ProofResultDto, DartSovereignError> {
+        if let DartProofBackend::Smt = backend {
+            return Err(DartSovereignError::SolverUnavailable("smt".to_string()));
+        }
+
+        let started = Instant::now();
+        let snapshot = self
+            .inner
+            .try_get()
+            .cloned()
+            .map_err(|_| DartSovereignError::Exiled)?;
+
+        let total = self.invariants.len().max(1) as f64;
+        let mut failed = Vec::new();
+        for (index, (name, rule)) in self.invariants.iter().enumerate() {
+            if !rule.holds(&snapshot) {
+                failed.push(name.clone());
+            }
+            if !on_progress((index + 1) as f64 / total) {
+                return Err(DartSovereignError::Cancelled);
+            }
+        }
+        if self.invariants.is_empty() && !on_progress(1.0) {
+            return Err(DartSovereignError::Cancelled);
+        }
+
+        Ok(ProofResultDto {
+            verified: failed.is_empty(),
+            checked: self.invariants.len() as u32,
+            failed,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_value_domestic() {
+        let mut sovereign = DartSovereignString::new("old".to_string());
+        sovereign.set_value("new".to_string()).unwrap();
+        assert_eq!(sovereign.get_value(), "new");
+    }
+
+    #[test]
+    fn test_set_value_exiled() {
+        let mut sovereign = DartSovereignString::new("old".to_string());
+        sovereign.annex("2a".to_string()).unwrap();
+        assert!(matches!(
+            sovereign.set_value("new".to_string()),
+            Err(DartSovereignError::Exiled)
+        ));
+    }
+
+    #[test]
+    fn test_parse_hex_id() {
+        assert_eq!(parse_hex_id("2a").unwrap(), 0x2a);
+        assert_eq!(parse_hex_id(" 0x2A ").unwrap(), 0x2a);
+        assert!(matches!(
+            parse_hex_id("peer"),
+            Err(DartSovereignError::InvalidId(raw)) if raw == "peer"
+        ));
+    }
+
+    #[test]
+    fn test_grant_lease() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let lease = sovereign.grant_lease("0x2a".to_string(), 60_000).unwrap();
+        assert_eq!(lease.holder, "2a");
+        assert!(lease.remaining_ms <= 60_000 && lease.remaining_ms > 59_000);
+
+        assert!(matches!(
+            sovereign.grant_lease("2b".to_string(), 60_000),
+            Err(DartSovereignError::AlreadyLeased)
+        ));
+    }
+
+    #[test]
+    fn test_grant_lease_invalid_peer() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        assert!(matches!(
+            sovereign.grant_lease("not hex".to_string(), 60_000),
+            Err(DartSovereignError::InvalidId(_))
+        ));
+    }
+
+    #[test]
+    fn test_set_value_while_leased() {
+        let mut sovereign = DartSovereignString::new("value".to_string());
+        sovereign.grant_lease("2a".to_string(), 60_000).unwrap();
+        assert!(matches!(
+            sovereign.set_value("new".to_string()),
+            Err(DartSovereignError::Exiled)
+        ));
+    }
+
+    #[test]
+    fn test_reclaim_after_expiry() {
+        let mut sovereign = DartSovereignString::new("value".to_string());
+        sovereign.grant_lease("2a".to_string(), 1).unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(sovereign.lease_info().unwrap().remaining_ms, 0);
+
+        sovereign.reclaim();
+        assert!(sovereign.lease_info().is_none());
+        sovereign.set_value("new".to_string()).unwrap();
+        assert_eq!(sovereign.get_value(), "new");
+    }
+
+    #[test]
+    fn test_lease_info_counts_down_from_grant() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        assert!(sovereign.lease_info().is_none());
+
+        let granted = sovereign.grant_lease("2a".to_string(), 60_000).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        let info = sovereign.lease_info().unwrap();
+        assert_eq!(info.lease_id, granted.lease_id);
+        assert_eq!(info.epoch, granted.epoch);
+        assert!(info.remaining_ms < granted.remaining_ms);
+
+        // Core ignores reclaim while the lease runs, so the record stays
+        sovereign.reclaim();
+        assert!(sovereign.lease_info().is_some());
+    }
+
+    /// Runs a watcher on its own thread, handing each event over a rendezvous channel so
+    /// the watcher stalls until the test takes it.
+    fn watch(sovereign: &DartSovereignString) -> std::sync::mpsc::Receiver<SovereignStateDto> {
+        let (events, received) = std::sync::mpsc::sync_channel(0);
+        let state = Arc::clone(&sovereign.state);
+        std::thread::spawn(move || state.forward(|dto| events.send(dto).is_ok()));
+        received
+    }
+
+    fn next(events: &std::sync::mpsc::Receiver<SovereignStateDto>) -> SovereignStateDto {
+        events.recv_timeout(Duration::from_secs(5)).unwrap()
+    }
+
+    #[test]
+    fn test_watch_state_through_lease_cycle() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let events = watch(&sovereign);
+        assert_eq!(next(&events).state, DartJurisdiction::Domestic);
+
+        sovereign.grant_lease("2a".to_string(), 200).unwrap();
+        let leased = next(&events);
+        assert_eq!(leased.state, DartJurisdiction::Leased);
+        assert_eq!(leased.holder.as_deref(), Some("2a"));
+        assert!(leased.remaining_ms.unwrap() <= 200);
+
+        let lapsed = next(&events);
+        assert_eq!(lapsed.state, DartJurisdiction::Lapsed);
+        assert_eq!(lapsed.remaining_ms, Some(0));
+        assert_eq!(lapsed.missed, 0);
+
+        sovereign.reclaim();
+        assert_eq!(next(&events).state, DartJurisdiction::Domestic);
+
+        drop(sovereign);
+        assert!(events.recv_timeout(Duration::from_secs(5)).is_err());
+    }
+
+    #[test]
+    fn test_watch_state_skips_to_latest_for_slow_listener() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let (events, received) = std::sync::mpsc::channel();
+        let (release, gate) = std::sync::mpsc::channel::<()>();
+        let state = Arc::clone(&sovereign.state);
+        std::thread::spawn(move || {
+            state.forward(|dto| {
+                let first = events.send(dto).is_ok();
+                // Stall after the initial state as a slow Dart listener would
+                gate.recv().is_ok() && first
+            })
+        });
+        assert_eq!(next(&received).state, DartJurisdiction::Domestic);
+
+        let token = sovereign.annex("2a".to_string()).unwrap();
+        sovereign.repatriate(&token).unwrap();
+        sovereign.annex("2a".to_string()).unwrap();
+        release.send(()).unwrap();
+
+        let latest = next(&received);
+        assert_eq!(latest.state, DartJurisdiction::Exiled);
+        assert_eq!(latest.missed, 2);
+    }
+
+    #[test]
+    fn test_annex_repatriate_round_trip() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let token = sovereign.annex("2a".to_string()).unwrap();
+        assert!(sovereign.is_exiled());
+        assert_eq!(sovereign.get_value(), "<Exiled>");
+
+        sovereign.repatriate(&token).unwrap();
+        assert!(!sovereign.is_exiled());
+        assert_eq!(sovereign.get_value(), "value");
+    }
+
+    #[test]
+    fn test_token_carries_holder() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let token = sovereign.annex("0x2A".to_string()).unwrap();
+        assert_eq!(token.holder(), "2a");
+        assert!(matches!(
+            sovereign.annex("peer".to_string()),
+            Err(DartSovereignError::InvalidId(_))
+        ));
+    }
+
+    #[test]
+    fn test_history() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let token = sovereign.annex("2a".to_string()).unwrap();
+        sovereign.repatriate(&token).unwrap();
+        let lease = sovereign.grant_lease("2b".to_string(), 1).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        sovereign.reclaim();
+
+        let history = sovereign.history();
+        let kinds: Vec<_> = history.iter().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                DartJurisdictionEventKind::Annexed,
+                DartJurisdictionEventKind::Repatriated,
+                DartJurisdictionEventKind::Leased,
+                DartJurisdictionEventKind::Lapsed,
+                DartJurisdictionEventKind::Reclaimed,
+            ]
+        );
+        assert_eq!(history[0].epoch, 0);
+        assert!(history[2..].iter().all(|event| event.epoch == lease.epoch));
+        assert!(
+            history
+                .windows(2)
+                .all(|pair| pair[0].timestamp_ms <= pair[1].timestamp_ms)
+        );
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        for _ in 0..HISTORY_LIMIT {
+            let token = sovereign.annex("2a".to_string()).unwrap();
+            sovereign.repatriate(&token).unwrap();
+        }
+        let history = sovereign.history();
+        assert_eq!(history.len(), HISTORY_LIMIT);
+        assert_eq!(history[0].kind, DartJurisdictionEventKind::Annexed);
+    }
+
+    #[test]
+    fn test_double_repatriation_rejected() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let token = sovereign.annex("2a".to_string()).unwrap();
+        sovereign.repatriate(&token).unwrap();
+
+        assert!(matches!(
+            sovereign.repatriate(&token),
+            Err(DartSovereignError::TokenAlreadyUsed)
+        ));
+    }
+
+    #[test]
+    fn test_foreign_token_rejected_and_left_unspent() {
+        let first = DartSovereignString::new("first".to_string());
+        let second = DartSovereignString::new("second".to_string());
+        let first_token = first.annex("2a".to_string()).unwrap();
+        second.annex("2a".to_string()).unwrap();
+
+        assert!(matches!(
+            second.repatriate(&first_token),
+            Err(DartSovereignError::ForgedToken(_))
+        ));
+        assert!(second.is_exiled());
+
+        first.repatriate(&first_token).unwrap();
+        assert!(!first.is_exiled());
+    }
+
+    #[test]
+    fn test_stale_token_rejected_after_reannex() {
+        let sovereign = DartSovereignString::new("value".to_string());
+        let stale = sovereign.annex("2a".to_string()).unwrap();
+        sovereign.repatriate(&stale).unwrap();
+        let current = sovereign.annex("2a".to_string()).unwrap();
+
+        assert_ne!(stale.to_hex(), current.to_hex());
+        assert!(sovereign.repatriate(&stale).is_err());
+        sovereign.repatriate(&current).unwrap();
+    }
+
+    fn account() -> DartSovereignJson {
+        let mut sovereign =
+            DartSovereignJson::new(r#"{"account":{"balance":10,"owner":"ana"}}"#.to_string())
+                .unwrap();
+        sovereign
+            .add_invariant(
+                "non_negative".to_string(),
+                "account.balance >= 0".to_string(),
+            )
+            .unwrap();
+        sovereign
+            .add_invariant(
+                "has_owner".to_string(),
+                "required account.owner".to_string(),
+            )
+            .unwrap();
+        sovereign
+    }
+
+    #[test]
+    fn test_update_accepted() {
+        let mut sovereign = account();
+        sovereign
+            .update_json(r#"{"account":{"balance":25,"owner":"ana"}}"#.to_string())
+            .unwrap();
+        assert_eq!(
+            sovereign.get_json().unwrap(),
+            r#"{"account":{"balance":25,"owner":"ana"}}"#
+        );
+    }
+
+    #[test]
+    fn test_update_rejected_with_two_violations() {
+        let mut sovereign = account();
+        let err = sovereign
+            .update_json(r#"{"account":{"balance":-5}}"#.to_string())
+            .unwrap_err();
+
+        match err {
+            DartSovereignError::InvariantViolation { failed } => {
+                assert_eq!(failed, ["non_negative", "has_owner"]);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(sovereign.get_json().unwrap().contains("\"balance\":10"));
+    }
+
+    #[test]
+    fn test_parse_error_names_byte_offset() {
+        let mut sovereign = account();
+        let err = sovereign
+            .update_json("{\"account\":\n  {\"balance\": }}".to_string())
+            .unwrap_err();
+
+        match err {
+            DartSovereignError::InvalidJson { offset, .. } => assert_eq!(offset, 26),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_rule() {
+        let mut sovereign = account();
+        assert!(matches!(
+            sovereign.add_invariant("bad".to_string(), "account.balance ~ 3".to_string()),
+            Err(DartSovereignError::InvalidRule { name, .. }) if name == "bad"
+        ));
+        assert!(JsonRule::parse("a..b > 1").is_err());
+        assert!(JsonRule::parse("a.b > ten").is_err());
+    }
+
+    #[test]
+    fn test_verify_integrity() {
+        let result = account().verify_integrity().unwrap();
+        assert!(result.verified);
+        assert_eq!(result.checked, 2);
+        assert!(result.failed.is_empty());
+    }
+
+    #[test]
+    fn test_verify_integrity_refuted() {
+        let mut sovereign = account();
+        sovereign
+            .add_invariant("rich".to_string(), "account.balance > 100".to_string())
+            .unwrap();
+
+        let result = sovereign.verify_integrity().unwrap();
+        assert!(!result.verified);
+        assert_eq!(result.checked, 3);
+        assert_eq!(result.failed, ["rich"]);
+    }
+
+    #[test]
+    fn test_verify_integrity_smt_unavailable() {
+        assert!(matches!(
+            account().verify_integrity_with_backend(DartProofBackend::Smt),
+            Err(DartSovereignError::SolverUnavailable(_))
+        ));
+    }
+
+    #[test]
+    fn test_verification_progress() {
+        let mut progress = Vec::new();
+        let result = account()
+            .run_verification(DartProofBackend::Empirical, |p| {
+                progress.push(p);
+                true
+            })
+            .unwrap();
+        assert!(result.verified);
+        assert_eq!(progress, [0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_verification_progress_refuted() {
+        let mut sovereign = account();
+        sovereign
+            .update_json(r#"{"account":{"balance":0,"owner":"ana"}}"#.to_string())
+            .unwrap();
+        sovereign
+            .add_invariant("positive".to_string(), "account.balance > 0".to_string())
+            .unwrap();
+
+        let mut reports = 0;
+        let result = sovereign
+            .run_verification(DartProofBackend::Empirical, |_| {
+                reports += 1;
+                true
+            })
+            .unwrap();
+        assert!(!result.verified);
+        assert_eq!(result.failed, ["positive"]);
+        assert_eq!(reports, 3);
+    }
+
+    #[test]
+    fn test_verification_cancelled() {
+        let mut reports = 0;
+        let result = account().run_verification(DartProofBackend::Empirical, |_| {
+            reports += 1;
+            false
+        });
+        assert!(matches!(result, Err(DartSovereignError::Cancelled)));
+        assert_eq!(reports, 1);
+    }
+
+    #[test]
+    fn test_byte_offset() {
+        assert_eq!(byte_offset("abc", 1, 2), 1);
+        assert_eq!(byte_offset("ab\ncd", 2, 1), 3);
+        assert_eq!(byte_offset("ab", 5, 9), 2);
     }
 }
@@ -0,0 +1,440 @@
+use crate::frb_generated::StreamSink;
+use flutter_rust_bridge::frb;
+use praborrow_lease::grpc::proto::Empty;
+use praborrow_lease::grpc::proto::control_plane_client::ControlPlaneClient;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint};
+
+/// Errors surfaced to Dart by `DartClusterClient`.
+#[derive(Debug, thiserror::Error)]
+pub enum DartClusterError {
+    #[error("Invalid endpoint {endpoint}: {reason}")]
+    InvalidEndpoint { endpoint: String, reason: String },
+    #[error("Invalid auth token: {0}")]
+    InvalidAuthToken(String),
+    #[error("TLS configuration error: {0}")]
+    Tls(String),
+    #[error("Network error: {message}")]
+    Network { message: String, retryable: bool },
+}
+
+impl From<tonic::Status> for DartClusterError {
+    fn from(status: tonic::Status) -> Self {
+        use tonic::Code;
+        let retryable = matches!(
+            status.code(),
+            Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted | Code::Aborted
+        );
+        Self::Network {
+            message: status.to_string(),
+            retryable,
+        }
+    }
+}
+
+impl From<tonic::transport::Error> for DartClusterError {
+    fn from(err: tonic::transport::Error) -> Self {
+        Self::Network {
+            message: err.to_string(),
+            retryable: true,
+        }
+    }
+}
+
+impl DartClusterError {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            DartClusterError::Network {
+                retryable: true,
+                ..
+            }
+        )
+    }
+}
+
+/// Optional TLS settings. The CA certificate is PEM-encoded.
+pub struct DartTlsConfig {
+    pub ca_certificate_pem: Option<String>,
+    pub domain_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DartConnectionState {
+    Disconnected,
+    Connecting,
+    Connected,
+}
+
+pub struct DartNodeStatus {
+    pub endpoint: String,
+    pub state: String,
+    pub current_term: u64,
+}
+
+/// Read-only client for the lease control plane.
+///
+/// Keeps one gRPC client per endpoint. Each call tries the endpoints in order; a
+/// retryable failure evicts that endpoint's client and the call fails over to the next.
+#[frb(opaque)]
+pub struct DartClusterClient {
+    endpoints: Vec<Endpoint>,
+    /// `authorization` header value, validated when the client is built.
+    authorization: Option<MetadataValue<Ascii>>,
+    /// Connected clients keyed by endpoint URI.
+    pool: Mutex<HashMap<String, ControlPlaneClient<Channel>>>,
+    state: Mutex<DartConnectionState>,
+    listeners: Mutex<Vec<StreamSink<DartConnectionState>>>,
+}
+
+impl DartClusterClient {
+    #[frb(sync)]
+    pub fn new(
+        endpoints: Vec<String>,
+        tls: Option<DartTlsConfig>,
+        auth_token: Option<String>,
+    ) -> Result<Self, DartClusterError> {
+        if endpoints.is_empty() {
+            return Err(DartClusterError::InvalidEndpoint {
+                endpoint: String::new(),
+                reason: "at least one endpoint is required".to_string(),
+            });
+        }
+
+        let authorization = auth_token
+            .map(|token| {
+                format!("Bearer {}", token)
+                    .parse::<MetadataValue<Ascii>>()
+                    .map_err(|e| DartClusterError::InvalidAuthToken(e.to_string()))
+            })
+            .transpose()?;
+        let tls = tls.map(build_tls_config);
+        let endpoints = endpoints
+            .into_iter()
+            .map(|raw| {
+                let endpoint = Endpoint::from_shared(raw.clone()).map_err(|e| {
+                    DartClusterError::InvalidEndpoint {
+                        endpoint: raw.clone(),
+                        reason: e.to_string(),
+                    }
+                })?;
+                match &tls {
+                    Some(config) => endpoint
+                        .tls_config(config.clone())
+                        .map_err(|e| DartClusterError::Tls(e.to_string())),
+                    None => Ok(endpoint),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            endpoints,
+            authorization,
+            pool: Mutex::new(HashMap::new()),
+            state: Mutex::new(DartConnectionState::Disconnected),
+            listeners: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Streams the current connection state followed by every change.
+    #[frb(sync)]
+    pub fn watch_connection(&self, sink: StreamSink<DartConnectionState>) {
+        let current = *self.state.lock().unwrap_or_else(|p| p.into_inner());
+        if sink.add(current).is_ok() {
+            self.listeners
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .push(sink);
+        }
+    }
+
+    pub async fn get_status(&self) -> Result<DartNodeStatus, DartClusterError> {
+        let (endpoint, status) = self
+            .call(Empty {}, |mut client, request| async move {
+                client.get_node_status(request).await
+            })
+            .await?;
+        Ok(DartNodeStatus {
+            endpoint,
+            state: status.state,
+            current_term: status.current_term,
+        })
+    }
+
+    pub async fn get_deadlocks(&self) -> Result<Vec<String>, DartClusterError> {
+        let (_, response) = self
+            .call(Empty {}, |mut client, request| async move {
+                client.get_deadlocks(request).await
+            })
+            .await?;
+        Ok(response.deadlocks)
+    }
+
+    fn request<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        if let Some(value) = &self.authorization {
+            request.metadata_mut().insert("authorization", value.clone());
+        }
+        request
+    }
+
+    /// Runs `rpc` against the endpoints in order and returns the answering endpoint with
+    /// the response.
+    ///
+    /// Connection failures and retryable statuses evict the endpoint from the pool and
+    /// move on to the next one; any other status is returned as is.
+    async fn call<M, T, F, Fut>(&self, message: M, rpc: F) -> Result<(String, T), DartClusterError>
+    where
+        M: Clone,
+        F: Fn(ControlPlaneClient<Channel>, tonic::Request<M>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+    {
+        let mut last_error = None;
+        for endpoint in &self.endpoints {
+            let key = endpoint.uri().to_string();
+            let client = match self.pooled(&key) {
+                Some(client) => client,
+                None => {
+                    self.set_state(DartConnectionState::Connecting);
+                    match endpoint.connect().await {
+                        Ok(channel) => {
+                            let client = ControlPlaneClient::new(channel);
+                            self.pool
+                                .lock()
+                                .unwrap_or_else(|p| p.into_inner())
+                                .insert(key.clone(), client.clone());
+                            client
+                        }
+                        Err(e) => {
+                            last_error = Some(DartClusterError::from(e));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            match rpc(client, self.request(message.clone())).await {
+                Ok(response) => {
+                    self.set_state(DartConnectionState::Connected);
+                    return Ok((key, response.into_inner()));
+                }
+                Err(status) => {
+                    let err = DartClusterError::from(status);
+                    if !err.is_retryable() {
+                        return Err(err);
+                    }
+                    self.evict(&key);
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        self.set_state(DartConnectionState::Disconnected);
+        Err(last_error.unwrap_or(DartClusterError::Network {
+            message: "no endpoints reachable".to_string(),
+            retryable: true,
+        }))
+    }
+
+    fn pooled(&self, endpoint: &str) -> Option<ControlPlaneClient<Channel>> {
+        self.pool
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .get(endpoint)
+            .cloned()
+    }
+
+    fn evict(&self, endpoint: &str) {
+        self.pool
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .remove(endpoint);
+    }
+
+    fn set_state(&self, next: DartConnectionState) {
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        if *state == next {
+            return;
+        }
+        *state = next;
+        self.listeners
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .retain(|sink| sink.add(next).is_ok());
+    }
+}
+
+fn build_tls_config(tls: DartTlsConfig) -> ClientTlsConfig {
+    let mut config = ClientTlsConfig::new();
+    if let Some(pem) = tls.ca_certificate_pem {
+        config = config.ca_certificate(Certificate::from_pem(pem));
+    }
+    if let Some(domain) = tls.domain_name {
+        config = config.domain_name(domain);
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use praborrow_lease::grpc::proto::control_plane_server::{ControlPlane, ControlPlaneServer};
+    use praborrow_lease::grpc::proto::{DeadlockResponse, LogRequest, LogResponse, NodeStatus};
+    use tokio_stream::wrappers::TcpListenerStream;
+
+    const TOKEN: &str = "secret";
+
+    /// Control plane that reports `state` and rejects requests without the bearer token.
+    struct Node {
+        state: &'static str,
+    }
+
+    #[tonic::async_trait]
+    impl ControlPlane for Node {
+        async fn get_node_status(
+            &self,
+            request: tonic::Request<Empty>,
+        ) -> Result<tonic::Response<NodeStatus>, tonic::Status> {
+            if !authorized(&request) {
+                return Err(tonic::Status::unauthenticated("missing bearer token"));
+            }
+            Ok(tonic::Response::new(NodeStatus {
+                state: self.state.to_string(),
+                current_term: 3,
+                ..Default::default()
+            }))
+        }
+
+        async fn get_recent_logs(
+            &self,
+            _request: tonic::Request<LogRequest>,
+        ) -> Result<tonic::Response<LogResponse>, tonic::Status> {
+            Ok(tonic::Response::new(LogResponse::default()))
+        }
+
+        async fn get_deadlocks(
+            &self,
+            request: tonic::Request<Empty>,
+        ) -> Result<tonic::Response<DeadlockResponse>, tonic::Status> {
+            if !authorized(&request) {
+                return Err(tonic::Status::unauthenticated("missing bearer token"));
+            }
+            Ok(tonic::Response::new(DeadlockResponse {
+                deadlocks: vec!["A -> B -> A".to_string()],
+            }))
+        }
+    }
+
+    fn authorized<T>(request: &tonic::Request<T>) -> bool {
+        request
+            .metadata()
+            .get("authorization")
+            .is_some_and(|value| value == format!("Bearer {}", TOKEN).as_str())
+    }
+
+    /// Serves a `Node` on an ephemeral port and returns its URI.
+    async fn serve(state: &'static str) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(ControlPlaneServer::new(Node { state }))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        format!("http://{}", addr)
+    }
+
+    /// URI of a port nothing listens on.
+    fn dead_endpoint() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn client(endpoints: Vec<String>, auth_token: Option<&str>) -> DartClusterClient {
+        DartClusterClient::new(endpoints, None, auth_token.map(str::to_string)).unwrap()
+    }
+
+    fn pooled(client: &DartClusterClient) -> Vec<String> {
+        let mut keys: Vec<_> = client.pool.lock().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_past_dead_endpoint() {
+        let live = serve("Leader").await;
+        let client = client(vec![dead_endpoint(), live.clone()], Some(TOKEN));
+
+        let status = client.get_status().await.unwrap();
+        assert!(status.endpoint.starts_with(&live));
+        assert_eq!(status.state, "Leader");
+        assert_eq!(status.current_term, 3);
+        assert_eq!(
+            client.get_deadlocks().await.unwrap(),
+            vec!["A -> B -> A".to_string()]
+        );
+
+        // Both calls reused the one pooled client
+        assert_eq!(pooled(&client), vec![status.endpoint]);
+        assert_eq!(
+            *client.state.lock().unwrap(),
+            DartConnectionState::Connected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_status_does_not_fail_over() {
+        let first = serve("Leader").await;
+        let second = serve("Follower").await;
+        let client = client(vec![first.clone(), second], None);
+
+        match client.get_status().await {
+            Err(DartClusterError::Network { retryable, .. }) => assert!(!retryable),
+            _ => panic!("expected an unauthenticated error"),
+        }
+        // The endpoint answered, so its client stays pooled and the second is never dialed
+        let pooled = pooled(&client);
+        assert_eq!(pooled.len(), 1);
+        assert!(pooled[0].starts_with(&first));
+    }
+
+    #[tokio::test]
+    async fn test_all_endpoints_down_is_retryable() {
+        let client = client(vec![dead_endpoint(), dead_endpoint()], Some(TOKEN));
+
+        let err = client.get_status().await.err().unwrap();
+        assert!(err.is_retryable());
+        assert!(pooled(&client).is_empty());
+        assert_eq!(
+            *client.state.lock().unwrap(),
+            DartConnectionState::Disconnected
+        );
+    }
+
+    #[test]
+    fn test_rejects_missing_and_invalid_endpoints() {
+        assert!(matches!(
+            DartClusterClient::new(Vec::new(), None, None),
+            Err(DartClusterError::InvalidEndpoint { .. })
+        ));
+        assert!(matches!(
+            DartClusterClient::new(vec!["not a uri".to_string()], None, None),
+            Err(DartClusterError::InvalidEndpoint { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_auth_token_that_is_not_a_header_value() {
+        assert!(matches!(
+            DartClusterClient::new(
+                vec!["http://127.0.0.1:1".to_string()],
+                None,
+                Some("line\nbreak".to_string())
+            ),
+            Err(DartClusterError::InvalidAuthToken(_))
+        ));
+    }
+}
@@ -1 +1,2 @@
 pub mod api;
+mod frb_generated;
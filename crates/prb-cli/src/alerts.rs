@@ -0,0 +1,329 @@
+//! Alerts raised when the cluster changes under an unattended dashboard.
+
+use crate::app::ConnectionStatus;
+use crate::cluster::ClusterView;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Minimum gap between two alerts of the same kind, so a flapping link doesn't spam.
+pub const ALERT_DEBOUNCE: Duration = Duration::from_secs(30);
+
+/// How long the header flashes after a bell alert.
+pub const FLASH_DURATION: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    Deadlock,
+    LeaderChange,
+    Disconnected,
+}
+
+/// Webhook payload; also the line logged to the Log Explorer.
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub event: AlertKind,
+    pub node: String,
+    pub details: String,
+    /// Unix time in milliseconds.
+    pub timestamp: u64,
+}
+
+impl Alert {
+    fn new(event: AlertKind, node: &str, details: String) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        Self {
+            event,
+            node: node.to_string(),
+            details,
+            timestamp,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!("ALERT [{}] {}", self.node, self.details)
+    }
+}
+
+/// Diffs successive observations into alerts, debounced per kind.
+#[derive(Debug)]
+pub struct AlertEngine {
+    debounce: Duration,
+    last_fired: HashMap<AlertKind, Instant>,
+    /// `None` until the first observation, so startup state never alerts.
+    known_deadlocks: Option<HashSet<String>>,
+    leader: Option<u64>,
+    connected: HashMap<String, bool>,
+}
+
+impl AlertEngine {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            last_fired: HashMap::new(),
+            known_deadlocks: None,
+            leader: None,
+            connected: HashMap::new(),
+        }
+    }
+
+    /// Alerts on cycles that were not reported by the previous poll of `node`.
+    pub fn observe_deadlocks(
+        &mut self,
+        node: &str,
+        deadlocks: &[String],
+        now: Instant,
+    ) -> Option<Alert> {
+        let current: HashSet<String> = deadlocks.iter().cloned().collect();
+        let previous = self.known_deadlocks.replace(current)?;
+        let mut fresh: Vec<&String> = deadlocks
+            .iter()
+            .filter(|cycle| !previous.contains(*cycle))
+            .collect();
+        if fresh.is_empty() {
+            return None;
+        }
+        fresh.sort();
+        fresh.dedup();
+        let details = format!(
+            "New deadlock: {}",
+            fresh
+                .iter()
+                .map(|cycle| cycle.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+        self.fire(Alert::new(AlertKind::Deadlock, node, details), now)
+    }
+
+    /// Alerts on a new leader and on nodes dropping from connected to disconnected.
+    pub fn observe_cluster(&mut self, cluster: &ClusterView, now: Instant) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        // Only a single agreed leader counts; elections and split brain keep the last one
+        if let [idx] = cluster.leaders().as_slice()
+            && let Some(status) = &cluster.nodes[*idx].status
+        {
+            let previous = self.leader.replace(status.node_id);
+            if let Some(previous) = previous
+                && previous != status.node_id
+            {
+                let details = format!(
+                    "Leader changed from node {} to node {} (term {})",
+                    previous, status.node_id, status.current_term
+                );
+                let node = &cluster.nodes[*idx].endpoint;
+                alerts.extend(self.fire(Alert::new(AlertKind::LeaderChange, node, details), now));
+            }
+        }
+
+        for node in &cluster.nodes {
+            let is_connected = node.connection == ConnectionStatus::Connected;
+            let was_connected = self
+                .connected
+                .insert(node.endpoint.clone(), is_connected)
+                .unwrap_or(false);
+            if was_connected && matches!(node.connection, ConnectionStatus::Disconnected(_)) {
+                let details = "Node transitioned to DISCONNECTED".to_string();
+                alerts.extend(self.fire(
+                    Alert::new(AlertKind::Disconnected, &node.endpoint, details),
+                    now,
+                ));
+            }
+        }
+
+        alerts
+    }
+
+    fn fire(&mut self, alert: Alert, now: Instant) -> Option<Alert> {
+        if let Some(last) = self.last_fired.get(&alert.event)
+            && now.duration_since(*last) < self.debounce
+        {
+            return None;
+        }
+        self.last_fired.insert(alert.event, now);
+        Some(alert)
+    }
+}
+
+/// POSTs alerts as JSON to the `--alert-webhook` URL.
+#[derive(Clone)]
+pub struct WebhookSender {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSender {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+
+    pub async fn send(&self, alert: &Alert) -> Result<(), reqwest::Error> {
+        self.client
+            .post(&self.url)
+            .json(alert)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cluster::NodeSample;
+    use crate::testing::{node_sample, strings};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn cluster(leader: u64, second: ConnectionStatus) -> ClusterView {
+        let role = |id| if leader == id { "Leader" } else { "Follower" };
+        let mut view = ClusterView::default();
+        view.update(vec![
+            node_sample("n1", 1, role(1), 2),
+            NodeSample {
+                connection: second,
+                ..node_sample("n2", 2, role(2), 2)
+            },
+        ]);
+        view
+    }
+
+    #[test]
+    fn test_startup_deadlocks_do_not_alert() {
+        let mut engine = AlertEngine::new(ALERT_DEBOUNCE);
+        let now = Instant::now();
+        assert!(
+            engine
+                .observe_deadlocks("n1", &strings(&["a -> b"]), now)
+                .is_none()
+        );
+        assert!(
+            engine
+                .observe_deadlocks("n1", &strings(&["a -> b"]), now)
+                .is_none()
+        );
+
+        let alert = engine
+            .observe_deadlocks("n1", &strings(&["c -> d", "a -> b", "c -> d"]), now)
+            .unwrap();
+        assert_eq!(alert.event, AlertKind::Deadlock);
+        assert_eq!(alert.describe(), "ALERT [n1] New deadlock: c -> d");
+    }
+
+    #[test]
+    fn test_alerts_are_debounced_per_kind() {
+        let mut engine = AlertEngine::new(Duration::from_secs(30));
+        let start = Instant::now();
+        engine.observe_deadlocks("n1", &[], start);
+        assert!(
+            engine
+                .observe_deadlocks("n1", &strings(&["x"]), start)
+                .is_some()
+        );
+        assert!(
+            engine
+                .observe_deadlocks("n1", &strings(&["y"]), start + Duration::from_secs(10))
+                .is_none()
+        );
+        assert!(
+            engine
+                .observe_deadlocks("n1", &strings(&["z"]), start + Duration::from_secs(31))
+                .is_some()
+        );
+
+        // Another kind is not held back by the deadlock debounce
+        engine.observe_cluster(&cluster(1, ConnectionStatus::Connected), start);
+        let alerts = engine.observe_cluster(&cluster(2, ConnectionStatus::Connected), start);
+        assert_eq!(alerts.len(), 1);
+    }
+
+    #[test]
+    fn test_leader_change_and_disconnect() {
+        let mut engine = AlertEngine::new(ALERT_DEBOUNCE);
+        let now = Instant::now();
+        assert!(
+            engine
+                .observe_cluster(&cluster(1, ConnectionStatus::Connected), now)
+                .is_empty()
+        );
+
+        let alerts = engine.observe_cluster(&cluster(2, ConnectionStatus::Connected), now);
+        assert_eq!(alerts[0].event, AlertKind::LeaderChange);
+        assert_eq!(alerts[0].node, "n2");
+        assert_eq!(
+            alerts[0].details,
+            "Leader changed from node 1 to node 2 (term 2)"
+        );
+
+        let alerts = engine.observe_cluster(&cluster(1, ConnectionStatus::Disconnected(now)), now);
+        // The switch back to node 1 falls inside the leader-change debounce
+        let kinds: Vec<_> = alerts.iter().map(|a| a.event).collect();
+        assert_eq!(kinds, [AlertKind::Disconnected]);
+        assert_eq!(alerts[0].node, "n2");
+    }
+
+    #[test]
+    fn test_never_connected_node_does_not_alert() {
+        let mut engine = AlertEngine::new(ALERT_DEBOUNCE);
+        let now = Instant::now();
+        let alerts = engine.observe_cluster(&cluster(1, ConnectionStatus::Disconnected(now)), now);
+        assert!(alerts.is_empty());
+    }
+
+    /// Accepts one webhook request, answers `200 OK` and returns its body.
+    async fn receive_webhook(listener: tokio::net::TcpListener) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    socket
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                        .await
+                        .unwrap();
+                    return body.to_string();
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_alert_json() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = tokio::spawn(receive_webhook(listener));
+
+        let alert = Alert::new(
+            AlertKind::Disconnected,
+            "n2",
+            "Node transitioned to DISCONNECTED".into(),
+        );
+        WebhookSender::new(url).send(&alert).await.unwrap();
+
+        let body: serde_json::Value = serde_json::from_str(&received.await.unwrap()).unwrap();
+        assert_eq!(body["event"], "disconnected");
+        assert_eq!(body["node"], "n2");
+        assert_eq!(body["details"], "Node transitioned to DISCONNECTED");
+        assert_eq!(body["timestamp"], alert.timestamp);
+    }
+}
@@ -0,0 +1,587 @@
+use crate::Mode;
+use crate::client::normalize_endpoint;
+use crate::cluster::ClusterView;
+use crate::filter::LogFilter;
+use crate::graph::parse_cycle;
+use crate::inspect::{self, ResourceReport};
+use crate::keymap::{Keymap, KeymapStyle};
+use crate::logview::LogView;
+use crate::snapshot::Snapshot;
+use crate::source::Sample;
+use crate::theme::Theme;
+use crate::timeline::{self, DeadlockHistory, Incident};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+pub const TAB_COUNT: usize = 4;
+pub const TAB_TITLES: [&str; TAB_COUNT] = ["Overview", "Log Explorer", "Deadlocks", "Timeline"];
+pub const LOGS_TAB: usize = 1;
+pub const DEADLOCKS_TAB: usize = 2;
+pub const TIMELINE_TAB: usize = 3;
+pub const MAX_LOGS: usize = 1000;
+
+/// Data collected while the view is paused; applied in arrival order on resume.
+#[derive(Debug, Default)]
+pub struct PauseBuffer {
+    /// Oldest first; capped at `MAX_LOGS`, as resuming would truncate to that anyway.
+    logs: VecDeque<String>,
+    deadlocks: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    OfflineMode,
+    Connected,
+    Reconnecting(usize), // retries
+    Disconnected(Instant),
+}
+
+pub struct App {
+    pub mode: Mode,
+    pub tab_index: usize,
+    pub should_quit: bool,
+    pub logs: VecDeque<String>,
+    pub log_view: LogView,
+    /// File that `y` appends the selected log line to.
+    pub log_export: Option<PathBuf>,
+    pub deadlocks: Vec<String>,
+    pub deadlock_cursor: usize,
+    /// Deadlock incidents seen across polls; keeps growing while the view is paused.
+    pub deadlock_history: DeadlockHistory,
+    pub timeline_cursor: usize,
+    /// Time span the Timeline tab's axis covers, ending now.
+    pub timeline_horizon: Duration,
+    /// Result of the last copy, shown in the footer.
+    pub notice: Option<String>,
+    /// Freezes logs and deadlocks on screen; polling continues into `pause_buffer`.
+    pub paused: bool,
+    pub pause_buffer: PauseBuffer,
+    pub filter_input: String,
+    /// Compiled form of `filter_input`; rebuilt only when the input changes.
+    pub filter: LogFilter,
+    pub filter_error: Option<String>,
+    pub is_typing: bool,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// Key binding overlay opened with `?`.
+    pub show_help: bool,
+    /// Resource account opened with `i`.
+    pub inspection: Option<ResourceReport>,
+    pub connection_status: ConnectionStatus,
+    /// Normalized addresses of the watched nodes; the first one feeds logs and deadlocks.
+    pub endpoints: Vec<String>,
+    pub cluster: ClusterView,
+    pub alert_bell: bool,
+    pub alert_webhook: Option<String>,
+    /// Last bell alert and when it was raised; the header flashes it for a few seconds.
+    pub alert_flash: Option<(String, Instant)>,
+    /// Error shown above the tabs, e.g. an unreadable offline snapshot.
+    pub banner: Option<String>,
+    pub snapshot: Option<Snapshot>,
+    /// Log index the offline view is scrubbed to; `None` shows the whole snapshot.
+    pub scrub_index: Option<u64>,
+    /// Replay position for the header; `None` outside replay mode.
+    pub timeline: Option<String>,
+}
+
+impl App {
+    pub fn new(mode: Mode) -> Self {
+        let (connection_status, endpoints) = match &mode {
+            Mode::Online { addresses } => (
+                ConnectionStatus::Disconnected(Instant::now()),
+                addresses.iter().map(|a| normalize_endpoint(a)).collect(),
+            ),
+            Mode::Offline { .. } | Mode::Replay { .. } => {
+                (ConnectionStatus::OfflineMode, Vec::new())
+            }
+        };
+
+        Self {
+            mode,
+            tab_index: 0,
+            should_quit: false,
+            logs: VecDeque::from(vec![
+                "System initialized".to_string(),
+                "Ready to inspect".to_string(),
+            ]),
+            log_view: LogView::default(),
+            log_export: None,
+            deadlocks: Vec::new(),
+            deadlock_cursor: 0,
+            deadlock_history: DeadlockHistory::default(),
+            timeline_cursor: 0,
+            timeline_horizon: Duration::from_secs(timeline::DEFAULT_HORIZON_SECS),
+            notice: None,
+            paused: false,
+            pause_buffer: PauseBuffer::default(),
+            filter_input: String::new(),
+            filter: LogFilter::All,
+            filter_error: None,
+            is_typing: false,
+            keymap: Keymap::new(KeymapStyle::Default),
+            theme: Theme::default(),
+            show_help: false,
+            inspection: None,
+            connection_status,
+            endpoints,
+            cluster: ClusterView::default(),
+            alert_bell: false,
+            alert_webhook: None,
+            alert_flash: None,
+            banner: None,
+            snapshot: None,
+            scrub_index: None,
+            timeline: None,
+        }
+    }
+
+    pub fn next_tab(&mut self) {
+        self.tab_index = (self.tab_index + 1) % TAB_COUNT;
+    }
+
+    pub fn prev_tab(&mut self) {
+        self.tab_index = (self.tab_index + TAB_COUNT - 1) % TAB_COUNT;
+    }
+
+    /// Adds a line on top of the log buffer, keeping the Log Explorer selection stable.
+    pub fn push_log(&mut self, line: String) {
+        if self.log_matches(&line) {
+            self.log_view.on_prepended(1);
+        }
+        self.logs.push_front(line);
+        self.logs.truncate(MAX_LOGS);
+    }
+
+    /// Applies one sample from the data source.
+    pub fn apply(&mut self, sample: Sample) {
+        match sample {
+            Sample::Event { line } => self.record_log(line),
+            Sample::ServerLogs { lines } => {
+                for line in lines {
+                    self.record_log(line);
+                }
+            }
+            Sample::Cluster { nodes } => {
+                // The primary node's link drives the header
+                if let Some(primary) = nodes.first() {
+                    self.connection_status = primary.connection.clone();
+                }
+                self.cluster.update(nodes);
+            }
+            Sample::Deadlocks { cycles, .. } => {
+                self.deadlock_history.observe(&cycles, inspect::now_ms());
+                self.record_deadlocks(cycles);
+            }
+        }
+    }
+
+    /// Shows a collected line now, or buffers it while paused.
+    pub fn record_log(&mut self, line: String) {
+        if self.paused {
+            self.pause_buffer.logs.push_back(line);
+            if self.pause_buffer.logs.len() > MAX_LOGS {
+                self.pause_buffer.logs.pop_front();
+            }
+        } else {
+            self.push_log(line);
+        }
+    }
+
+    pub fn record_deadlocks(&mut self, deadlocks: Vec<String>) {
+        if self.paused {
+            self.pause_buffer.deadlocks = Some(deadlocks);
+        } else {
+            self.deadlocks = deadlocks;
+            self.deadlock_cursor = self
+                .deadlock_cursor
+                .min(self.deadlocks.len().saturating_sub(1));
+        }
+    }
+
+    /// Buffered log lines plus deadlocks not yet on screen, for the paused badge.
+    pub fn new_events(&self) -> usize {
+        let deadlocks = self.pause_buffer.deadlocks.as_ref().map_or(0, |pending| {
+            pending
+                .iter()
+                .filter(|cycle| !self.deadlocks.contains(cycle))
+                .count()
+        });
+        self.pause_buffer.logs.len() + deadlocks
+    }
+
+    /// Pauses the view, or resumes it and applies everything collected meanwhile.
+    pub fn toggle_pause(&mut self) {
+        if !self.paused {
+            self.paused = true;
+            return;
+        }
+        self.paused = false;
+        let buffer = std::mem::take(&mut self.pause_buffer);
+        for line in buffer.logs {
+            self.push_log(line);
+        }
+        if let Some(deadlocks) = buffer.deadlocks {
+            self.record_deadlocks(deadlocks);
+        }
+    }
+
+    pub fn log_matches(&self, line: &str) -> bool {
+        self.filter.matches(line)
+    }
+
+    pub fn filter_push(&mut self, c: char) {
+        self.filter_input.push(c);
+        self.refresh_filter();
+    }
+
+    pub fn filter_pop(&mut self) {
+        self.filter_input.pop();
+        self.refresh_filter();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter_input.clear();
+        self.refresh_filter();
+    }
+
+    /// Recompiles the filter; an invalid one matches everything and reports the error.
+    fn refresh_filter(&mut self) {
+        match LogFilter::parse(&self.filter_input) {
+            Ok(filter) => {
+                self.filter = filter;
+                self.filter_error = None;
+            }
+            Err(e) => {
+                self.filter = LogFilter::All;
+                self.filter_error = Some(e);
+            }
+        }
+    }
+
+    /// Log lines passing the current filter, newest first.
+    pub fn visible_logs(&self) -> Vec<&String> {
+        self.logs
+            .iter()
+            .filter(|line| self.log_matches(line))
+            .collect()
+    }
+
+    pub fn selected_log(&self) -> Option<String> {
+        let visible = self.visible_logs();
+        self.log_view
+            .selected(visible.len())
+            .map(|idx| visible[idx].clone())
+    }
+
+    /// Moves the row selection of the current tab.
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.tab_index == LOGS_TAB {
+            let len = self.visible_logs().len();
+            self.log_view.scroll(delta, len);
+            return;
+        }
+        let (cursor, len) = match self.tab_index {
+            DEADLOCKS_TAB => (&mut self.deadlock_cursor, self.deadlocks.len()),
+            TIMELINE_TAB => (&mut self.timeline_cursor, self.deadlock_history.len()),
+            _ => return,
+        };
+        *cursor = cursor
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    /// Incidents in the order the Timeline tab lists them, newest first.
+    pub fn timeline_incidents(&self) -> Vec<&Incident> {
+        self.deadlock_history.incidents().iter().rev().collect()
+    }
+
+    /// First resource of the selected cycle in the Deadlocks tab.
+    pub fn selected_resource(&self) -> Option<String> {
+        if self.tab_index != DEADLOCKS_TAB {
+            return None;
+        }
+        self.deadlocks
+            .get(self.deadlock_cursor)
+            .and_then(|cycle| parse_cycle(cycle).into_iter().next())
+    }
+
+    /// Report on `resource_id` from the deadlocks and log lines already collected.
+    pub fn inspect(&self, resource_id: &str) -> ResourceReport {
+        let (deadlocks, now_ms) = match &self.snapshot {
+            // Offline reports are as of the time the snapshot was taken
+            Some(snapshot) => (
+                snapshot
+                    .deadlocks
+                    .iter()
+                    .map(|d| Incident::detected(d.describe(), d.detected_at_ms))
+                    .collect(),
+                snapshot.taken_at_ms,
+            ),
+            None => (
+                self.deadlock_history.incidents().iter().cloned().collect(),
+                inspect::now_ms(),
+            ),
+        };
+        let responses = inspect::Responses {
+            deadlocks,
+            logs: self.logs.iter().cloned().collect(),
+        };
+        inspect::merge(resource_id, responses, now_ms)
+    }
+
+    /// Replaces the displayed data with the contents of an offline snapshot.
+    pub fn load_snapshot(&mut self, snapshot: Snapshot) {
+        self.scrub_index = snapshot.last_index();
+        self.snapshot = Some(snapshot);
+        self.banner = None;
+        self.apply_scrub();
+    }
+
+    /// Moves the offline view one log entry back in time.
+    pub fn scrub_back(&mut self) {
+        self.step_scrub(-1);
+    }
+
+    /// Moves the offline view one log entry forward in time.
+    pub fn scrub_forward(&mut self) {
+        self.step_scrub(1);
+    }
+
+    fn step_scrub(&mut self, delta: isize) {
+        let Some(snapshot) = &self.snapshot else {
+            return;
+        };
+        if snapshot.log.is_empty() {
+            return;
+        }
+        let current = self
+            .scrub_index
+            .and_then(|idx| snapshot.log.iter().position(|e| e.index == idx))
+            .unwrap_or(snapshot.log.len() - 1);
+        let next = current
+            .saturating_add_signed(delta)
+            .min(snapshot.log.len() - 1);
+        self.scrub_index = Some(snapshot.log[next].index);
+        self.apply_scrub();
+    }
+
+    /// Rebuilds logs and deadlocks from the snapshot as of `scrub_index`.
+    fn apply_scrub(&mut self) {
+        let Some(snapshot) = &self.snapshot else {
+            return;
+        };
+        let cutoff = self.scrub_index.unwrap_or(u64::MAX);
+
+        self.logs = snapshot
+            .log
+            .iter()
+            .filter(|entry| entry.index <= cutoff)
+            .rev()
+            .take(MAX_LOGS)
+            .map(|entry| format!("[{}] (term {}) {}", entry.index, entry.term, entry.command))
+            .collect();
+
+        self.deadlocks = snapshot
+            .deadlocks
+            .iter()
+            .filter(|incident| incident.log_index <= cutoff)
+            .map(|incident| incident.describe())
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{DeadlockIncident, LeaseEntry, LogEntry};
+
+    fn offline() -> App {
+        App::new(Mode::Offline {
+            path: PathBuf::from("snapshot.json"),
+        })
+    }
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            version: 1,
+            node_id: Some(1),
+            taken_at_ms: 10_000,
+            log: (1..=3)
+                .map(|index| LogEntry {
+                    index,
+                    term: 1,
+                    command: format!("cmd-{}", index),
+                })
+                .collect(),
+            leases: vec![LeaseEntry {
+                resource_id: "db".into(),
+                holder: "node-1".into(),
+                lease_id: "l-1".into(),
+                epoch: 4,
+                expires_at_ms: 12_500,
+            }],
+            deadlocks: vec![DeadlockIncident {
+                detected_at_ms: 9_000,
+                log_index: 3,
+                cycle: vec!["a".into(), "b".into()],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_load_snapshot_populates_views() {
+        let mut app = offline();
+        app.banner = Some("stale".into());
+        app.load_snapshot(snapshot());
+
+        assert_eq!(app.connection_status, ConnectionStatus::OfflineMode);
+        assert_eq!(app.scrub_index, Some(3));
+        assert_eq!(app.logs.front().unwrap(), "[3] (term 1) cmd-3");
+        assert_eq!(app.logs.len(), 3);
+        assert_eq!(app.deadlocks, vec!["a -> b -> a".to_string()]);
+        assert_eq!(app.banner, None);
+    }
+
+    #[test]
+    fn test_scrub_hides_later_entries() {
+        let mut app = offline();
+        app.load_snapshot(snapshot());
+
+        app.scrub_back();
+        assert_eq!(app.scrub_index, Some(2));
+        assert_eq!(app.logs.len(), 2);
+        assert!(app.deadlocks.is_empty());
+
+        app.scrub_back();
+        app.scrub_back();
+        assert_eq!(app.scrub_index, Some(1));
+
+        app.scrub_forward();
+        app.scrub_forward();
+        app.scrub_forward();
+        assert_eq!(app.scrub_index, Some(3));
+        assert_eq!(app.deadlocks.len(), 1);
+    }
+
+    #[test]
+    fn test_inspect_offline_uses_snapshot() {
+        let mut app = offline();
+        app.load_snapshot(snapshot());
+        app.push_log("lock wait on b".into());
+
+        let report = app.inspect("b");
+        assert_eq!(report.generated_at_ms, 10_000);
+        assert_eq!(report.events.len(), 1);
+        assert_eq!(report.events[0].at_ms, 9_000);
+        assert_eq!(report.log_lines, ["lock wait on b"]);
+    }
+
+    #[test]
+    fn test_scrub_without_snapshot_is_noop() {
+        let mut app = offline();
+        app.scrub_back();
+        assert_eq!(app.scrub_index, None);
+        assert_eq!(app.logs.len(), 2);
+    }
+
+    #[test]
+    fn test_pause_buffers_and_counts_new_events() {
+        let mut app = offline();
+        app.record_deadlocks(vec!["a -> b -> a".into()]);
+        app.toggle_pause();
+        assert!(app.paused);
+
+        app.record_log("one".into());
+        app.record_log("two".into());
+        app.record_deadlocks(vec!["a -> b -> a".into(), "c -> d -> c".into()]);
+
+        assert_eq!(app.logs.len(), 2);
+        assert_eq!(app.deadlocks.len(), 1);
+        // Two lines plus the one deadlock not yet shown
+        assert_eq!(app.new_events(), 3);
+    }
+
+    #[test]
+    fn test_pause_buffer_is_bounded() {
+        let mut app = offline();
+        app.toggle_pause();
+        for i in 0..MAX_LOGS + 5 {
+            app.record_log(format!("line {}", i));
+        }
+        assert_eq!(app.new_events(), MAX_LOGS);
+
+        app.toggle_pause();
+        assert_eq!(app.logs.len(), MAX_LOGS);
+        assert_eq!(app.logs[0], format!("line {}", MAX_LOGS + 4));
+        assert_eq!(app.logs[MAX_LOGS - 1], "line 5");
+    }
+
+    #[test]
+    fn test_resume_applies_buffer_in_order() {
+        let mut app = offline();
+        app.toggle_pause();
+        app.record_log("first".into());
+        app.record_log("second".into());
+        app.record_deadlocks(vec!["x".into()]);
+        app.record_deadlocks(vec!["y".into()]);
+
+        app.toggle_pause();
+        assert!(!app.paused);
+        assert_eq!(app.new_events(), 0);
+        assert_eq!(
+            app.logs.iter().take(2).collect::<Vec<_>>(),
+            ["second", "first"]
+        );
+        // Only the latest deadlock poll survives
+        assert_eq!(app.deadlocks, ["y"]);
+    }
+
+    #[test]
+    fn test_selected_resource_follows_deadlock_cursor() {
+        let mut app = offline();
+        app.record_deadlocks(vec![
+            "db -> cache -> db".into(),
+            "queue -> db -> queue".into(),
+        ]);
+        assert_eq!(app.selected_resource(), None);
+
+        app.tab_index = DEADLOCKS_TAB;
+        assert_eq!(app.selected_resource().as_deref(), Some("db"));
+        app.move_selection(1);
+        assert_eq!(app.selected_resource().as_deref(), Some("queue"));
+    }
+
+    #[test]
+    fn test_deadlock_history_records_while_paused() {
+        let mut app = offline();
+        app.toggle_pause();
+        app.apply(Sample::Deadlocks {
+            node: "node-1".into(),
+            cycles: vec!["a -> b -> a".into()],
+        });
+        app.apply(Sample::Deadlocks {
+            node: "node-1".into(),
+            cycles: vec![],
+        });
+
+        assert!(app.deadlocks.is_empty());
+        let incidents = app.timeline_incidents();
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].cycle, "a -> b -> a");
+        assert!(!incidents[0].is_active());
+    }
+
+    #[test]
+    fn test_resume_keeps_log_selection() {
+        let mut app = offline();
+        app.tab_index = LOGS_TAB;
+        app.move_selection(1);
+        assert_eq!(app.selected_log().as_deref(), Some("Ready to inspect"));
+
+        app.toggle_pause();
+        app.record_log("new".into());
+        app.toggle_pause();
+        assert_eq!(app.selected_log().as_deref(), Some("Ready to inspect"));
+    }
+}
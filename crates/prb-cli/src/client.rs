@@ -0,0 +1,247 @@
+//! Control-plane access shared by the dashboard and the one-shot commands.
+
+use praborrow_lease::grpc::proto::control_plane_client::ControlPlaneClient;
+use praborrow_lease::grpc::proto::{Empty, LogRequest};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+use tonic::transport::{Channel, Endpoint};
+
+/// Default `--rpc-timeout` in milliseconds.
+pub const DEFAULT_RPC_TIMEOUT_MS: u64 = 2000;
+
+/// Consecutive timed-out heartbeats after which a node is redialed.
+pub const MAX_MISSED_POLLS: u32 = 3;
+
+/// Adds the `http://` scheme when the user passed a bare `host:port`.
+pub fn normalize_endpoint(address: &str) -> String {
+    if address.starts_with("http://") || address.starts_with("https://") {
+        address.to_string()
+    } else {
+        format!("http://{}", address)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeStatus {
+    pub node_id: u64,
+    pub state: String,
+    pub current_term: u64,
+    pub commit_index: u64,
+}
+
+/// Thin wrapper over the generated gRPC client returning CLI-friendly types.
+///
+/// Every call is bounded by `timeout`, so a hung server cannot stall the caller.
+#[derive(Clone)]
+pub struct ControlPlane {
+    client: ControlPlaneClient<Channel>,
+    timeout: Duration,
+}
+
+impl ControlPlane {
+    pub async fn connect(
+        endpoint: &str,
+        timeout: Duration,
+    ) -> Result<Self, tonic::transport::Error> {
+        let channel = Endpoint::from_shared(normalize_endpoint(endpoint))?
+            .connect_timeout(timeout)
+            .connect()
+            .await?;
+        Ok(Self {
+            client: ControlPlaneClient::new(channel),
+            timeout,
+        })
+    }
+
+    pub async fn status(&mut self) -> Result<NodeStatus, tonic::Status> {
+        let status = deadline(
+            self.timeout,
+            self.client.get_node_status(tonic::Request::new(Empty {})),
+        )
+        .await?
+        .into_inner();
+        Ok(NodeStatus {
+            node_id: status.id,
+            state: status.state,
+            current_term: status.current_term,
+            commit_index: status.commit_index,
+        })
+    }
+
+    pub async fn recent_logs(&mut self, limit: u32) -> Result<Vec<String>, tonic::Status> {
+        Ok(deadline(
+            self.timeout,
+            self.client
+                .get_recent_logs(tonic::Request::new(LogRequest { limit })),
+        )
+        .await?
+        .into_inner()
+        .logs)
+    }
+
+    pub async fn deadlocks(&mut self) -> Result<Vec<String>, tonic::Status> {
+        Ok(deadline(
+            self.timeout,
+            self.client.get_deadlocks(tonic::Request::new(Empty {})),
+        )
+        .await?
+        .into_inner()
+        .deadlocks)
+    }
+}
+
+/// Bounds `call` by `timeout`; an expired deadline surfaces as `DeadlineExceeded`.
+async fn deadline<T>(
+    timeout: Duration,
+    call: impl Future<Output = Result<T, tonic::Status>>,
+) -> Result<T, tonic::Status> {
+    tokio::time::timeout(timeout, call)
+        .await
+        .unwrap_or_else(|_| {
+            Err(tonic::Status::deadline_exceeded(format!(
+                "no response within {}ms",
+                timeout.as_millis()
+            )))
+        })
+}
+
+pub fn is_timeout(status: &tonic::Status) -> bool {
+    status.code() == tonic::Code::DeadlineExceeded
+}
+
+/// What a heartbeat result means for the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Healthy,
+    /// Timed out, but not often enough in a row to give up on the connection.
+    Missed,
+    /// Timed out `limit` times in a row; the connection should be redialed.
+    Reconnect,
+    /// Any other error.
+    Failed,
+}
+
+/// Counts consecutive timed-out heartbeats against one node.
+///
+/// A slow server is treated as a soft failure: the dashboard keeps the connection and
+/// shows the count, and only redials once `limit` heartbeats in a row went unanswered.
+#[derive(Debug, Clone, Copy)]
+pub struct MissedPolls {
+    count: u32,
+    limit: u32,
+}
+
+impl MissedPolls {
+    pub fn new(limit: u32) -> Self {
+        Self { count: 0, limit }
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+
+    pub fn observe<T>(&mut self, result: &Result<T, tonic::Status>) -> Verdict {
+        match result {
+            Ok(_) => {
+                self.count = 0;
+                Verdict::Healthy
+            }
+            Err(status) if is_timeout(status) => {
+                self.count += 1;
+                if self.count >= self.limit {
+                    Verdict::Reconnect
+                } else {
+                    Verdict::Missed
+                }
+            }
+            Err(_) => Verdict::Failed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timed_out() -> Result<(), tonic::Status> {
+        Err(tonic::Status::deadline_exceeded("slow"))
+    }
+
+    #[test]
+    fn test_normalize_endpoint() {
+        assert_eq!(normalize_endpoint("node:50051"), "http://node:50051");
+        assert_eq!(normalize_endpoint("https://node"), "https://node");
+    }
+
+    #[test]
+    fn test_missed_polls_escalate_to_reconnect() {
+        let mut missed = MissedPolls::new(3);
+        assert_eq!(missed.observe(&timed_out()), Verdict::Missed);
+        assert_eq!(missed.observe(&timed_out()), Verdict::Missed);
+        assert_eq!(missed.count(), 2);
+        assert_eq!(missed.observe(&timed_out()), Verdict::Reconnect);
+        assert_eq!(missed.count(), 3);
+    }
+
+    #[test]
+    fn test_answered_poll_resets_count() {
+        let mut missed = MissedPolls::new(3);
+        missed.observe(&timed_out());
+        missed.observe(&timed_out());
+        assert_eq!(missed.observe(&Ok(())), Verdict::Healthy);
+        assert_eq!(missed.count(), 0);
+        assert_eq!(missed.observe(&timed_out()), Verdict::Missed);
+    }
+
+    #[test]
+    fn test_other_errors_are_not_missed_polls() {
+        let mut missed = MissedPolls::new(3);
+        let refused: Result<(), _> = Err(tonic::Status::unavailable("refused"));
+        assert_eq!(missed.observe(&refused), Verdict::Failed);
+        assert_eq!(missed.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_server_times_out() {
+        use crate::cluster::NodeLink;
+        use crate::testing::{self, MockNode};
+
+        let timeout = Duration::from_millis(100);
+        let address = testing::serve(MockNode {
+            stall: Some(Duration::from_secs(30)),
+            ..MockNode::leader(1)
+        })
+        .await;
+
+        let mut plane = ControlPlane::connect(&address, timeout).await.unwrap();
+        let status = plane.status().await.unwrap_err();
+        assert!(is_timeout(&status));
+        // Calls that don't stall still go through on the same connection
+        assert!(plane.deadlocks().await.unwrap().is_empty());
+
+        let mut link = NodeLink::new(address.clone(), timeout);
+        assert_eq!(
+            link.reconnect_if_due().await,
+            Some(format!("Connected to {}.", address))
+        );
+        for missed in 1..MAX_MISSED_POLLS {
+            let line = link.poll().await.unwrap();
+            assert!(line.ends_with(&format!("({} missed)", missed)), "{}", line);
+            assert_eq!(link.sample().missed_polls, missed);
+            assert!(link.responsive_client_mut().is_none());
+        }
+        let line = link.poll().await.unwrap();
+        assert!(
+            line.ends_with("heartbeats in a row; reconnecting"),
+            "{}",
+            line
+        );
+        assert!(link.responsive_client_mut().is_none());
+        assert_eq!(link.sample().missed_polls, 0);
+    }
+}
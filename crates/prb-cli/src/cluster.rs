@@ -0,0 +1,353 @@
+//! Per-node connections and the aggregated view behind the Overview tab.
+
+use crate::app::ConnectionStatus;
+use crate::client::{ControlPlane, MAX_MISSED_POLLS, MissedPolls, NodeStatus, Verdict};
+use crate::inspect::now_ms;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const MAX_BACKOFF_SECS: u64 = 30;
+
+/// Connection to one node plus the last data it reported.
+pub struct NodeLink {
+    pub endpoint: String,
+    client: Option<ControlPlane>,
+    /// Deadline for dialing and for every call on `client`.
+    rpc_timeout: Duration,
+    missed: MissedPolls,
+    pub connection: ConnectionStatus,
+    last_attempt: Option<Instant>,
+    backoff_secs: u64,
+    pub status: Option<NodeStatus>,
+    last_seen_ms: Option<u64>,
+}
+
+impl NodeLink {
+    pub fn new(endpoint: String, rpc_timeout: Duration) -> Self {
+        Self {
+            endpoint,
+            client: None,
+            rpc_timeout,
+            missed: MissedPolls::new(MAX_MISSED_POLLS),
+            connection: ConnectionStatus::Disconnected(Instant::now()),
+            last_attempt: None,
+            backoff_secs: 1,
+            status: None,
+            last_seen_ms: None,
+        }
+    }
+
+    /// The client, unless the last heartbeat went unanswered; further calls on this tick
+    /// would only stall it again.
+    pub fn responsive_client_mut(&mut self) -> Option<&mut ControlPlane> {
+        if self.missed.count() > 0 {
+            return None;
+        }
+        self.client.as_mut()
+    }
+
+    /// Drops the client after a failed call; the next reconnect starts over.
+    pub fn mark_disconnected(&mut self) {
+        self.client = None;
+        self.missed.reset();
+        self.connection = ConnectionStatus::Disconnected(Instant::now());
+    }
+
+    /// Connects when disconnected and the backoff has elapsed; returns a line for the event log.
+    pub async fn reconnect_if_due(&mut self) -> Option<String> {
+        if self.client.is_some() {
+            return None;
+        }
+        if let Some(last) = self.last_attempt
+            && last.elapsed() < Duration::from_secs(self.backoff_secs)
+        {
+            return None;
+        }
+        self.last_attempt = Some(Instant::now());
+        self.connection = ConnectionStatus::Reconnecting(self.backoff_secs as usize);
+
+        // The connect timeout covers TCP only; bound the HTTP/2 handshake as well
+        let connect = ControlPlane::connect(&self.endpoint, self.rpc_timeout);
+        let error = match tokio::time::timeout(self.rpc_timeout, connect).await {
+            Ok(Ok(client)) => {
+                self.client = Some(client);
+                self.connection = ConnectionStatus::Connected;
+                self.backoff_secs = 1;
+                return Some(format!("Connected to {}.", self.endpoint));
+            }
+            Ok(Err(e)) => e.to_string(),
+            Err(_) => "timed out".to_string(),
+        };
+        self.backoff_secs = (self.backoff_secs * 2).min(MAX_BACKOFF_SECS);
+        self.connection = ConnectionStatus::Disconnected(Instant::now());
+        Some(format!("Connection to {} failed: {}", self.endpoint, error))
+    }
+
+    /// Refreshes the node's status.
+    ///
+    /// A failed status call disconnects the node; a timed out one only counts as a missed
+    /// poll until [`MAX_MISSED_POLLS`] happen in a row.
+    pub async fn poll(&mut self) -> Option<String> {
+        let client = self.client.as_mut()?;
+        let result = client.status().await;
+        let status = match (self.missed.observe(&result), result) {
+            (_, Ok(status)) => status,
+            (Verdict::Missed, Err(_)) => {
+                return Some(format!(
+                    "Heartbeat to {} timed out ({} missed)",
+                    self.endpoint,
+                    self.missed.count()
+                ));
+            }
+            (Verdict::Reconnect, Err(_)) => {
+                self.mark_disconnected();
+                return Some(format!(
+                    "{} missed {} heartbeats in a row; reconnecting",
+                    self.endpoint, MAX_MISSED_POLLS
+                ));
+            }
+            (_, Err(e)) => {
+                self.mark_disconnected();
+                return Some(format!("Heartbeat to {} failed: {}", self.endpoint, e));
+            }
+        };
+        self.connection = ConnectionStatus::Connected;
+        let line = format!(
+            "STATUS {}: {} (Term {})",
+            self.endpoint, status.state, status.current_term
+        );
+        self.status = Some(status);
+        self.last_seen_ms = Some(now_ms());
+        Some(line)
+    }
+
+    pub fn sample(&self) -> NodeSample {
+        NodeSample {
+            endpoint: self.endpoint.clone(),
+            connection: self.connection.clone(),
+            status: self.status.clone(),
+            missed_polls: self.missed.count(),
+            last_seen_ms: self.last_seen_ms,
+        }
+    }
+}
+
+/// What the Overview table shows for one node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeSample {
+    pub endpoint: String,
+    #[serde(with = "crate::recording::connection_status")]
+    pub connection: ConnectionStatus,
+    /// Last reported status; kept while disconnected but ignored by the consistency checks.
+    pub status: Option<NodeStatus>,
+    /// Consecutive heartbeats that timed out; absent from older recordings.
+    #[serde(default)]
+    pub missed_polls: u32,
+    /// Unix ms of the last answered heartbeat; absent from older recordings.
+    #[serde(default)]
+    pub last_seen_ms: Option<u64>,
+}
+
+impl NodeSample {
+    fn live_status(&self) -> Option<&NodeStatus> {
+        match self.connection {
+            ConnectionStatus::Connected => self.status.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Why a node's row is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disagreement {
+    /// More than one reachable node claims leadership.
+    CompetingLeader,
+    /// The node reports an older term than the highest one seen.
+    StaleTerm,
+}
+
+impl Disagreement {
+    pub fn label(self) -> &'static str {
+        match self {
+            Disagreement::CompetingLeader => "competing leader",
+            Disagreement::StaleTerm => "stale term",
+        }
+    }
+}
+
+/// Cluster-wide view aggregated from every node the dashboard watches.
+#[derive(Debug, Default)]
+pub struct ClusterView {
+    pub nodes: Vec<NodeSample>,
+}
+
+impl ClusterView {
+    pub fn update(&mut self, nodes: Vec<NodeSample>) {
+        self.nodes = nodes;
+    }
+
+    pub fn connected(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| node.live_status().is_some())
+            .count()
+    }
+
+    /// Indices of reachable nodes that report themselves as leader.
+    pub fn leaders(&self) -> Vec<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| {
+                node.live_status()
+                    .is_some_and(|status| status.state.eq_ignore_ascii_case("leader"))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn highest_term(&self) -> Option<u64> {
+        self.nodes
+            .iter()
+            .filter_map(|node| node.live_status())
+            .map(|status| status.current_term)
+            .max()
+    }
+
+    /// Split-brain rules: every leader is flagged when there are several, and any node
+    /// behind the highest term is flagged. Unreachable nodes are never flagged.
+    pub fn disagreement(&self, idx: usize) -> Option<Disagreement> {
+        let status = self.nodes.get(idx)?.live_status()?;
+        let leaders = self.leaders();
+        if leaders.len() > 1 && leaders.contains(&idx) {
+            return Some(Disagreement::CompetingLeader);
+        }
+        if self
+            .highest_term()
+            .is_some_and(|term| status.current_term < term)
+        {
+            return Some(Disagreement::StaleTerm);
+        }
+        None
+    }
+
+    /// One-line verdict shown above the node table.
+    pub fn summary(&self) -> String {
+        let leaders = self.leaders().len();
+        let health = if leaders > 1 {
+            format!("SPLIT BRAIN: {} leaders", leaders)
+        } else if (0..self.nodes.len()).any(|idx| self.disagreement(idx).is_some()) {
+            "Terms diverge".to_string()
+        } else if leaders == 0 {
+            "No leader".to_string()
+        } else {
+            "Consensus: Stable".to_string()
+        };
+        format!(
+            "Nodes: {}/{} Online | {}",
+            self.connected(),
+            self.nodes.len(),
+            health
+        )
+    }
+
+    pub fn missed_polls(&self) -> u32 {
+        self.nodes.iter().map(|node| node.missed_polls).sum()
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.leaders().len() == 1
+            && (0..self.nodes.len()).all(|idx| self.disagreement(idx).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::node_sample;
+
+    fn node(state: &str, term: u64) -> NodeSample {
+        let endpoint = format!("http://{}-{}", state.to_lowercase(), term);
+        node_sample(&endpoint, 1, state, term)
+    }
+
+    fn unreachable(mut sample: NodeSample) -> NodeSample {
+        sample.connection = ConnectionStatus::Disconnected(Instant::now());
+        sample
+    }
+
+    fn view(nodes: Vec<NodeSample>) -> ClusterView {
+        let mut view = ClusterView::default();
+        view.update(nodes);
+        view
+    }
+
+    fn flags(view: &ClusterView) -> Vec<Option<Disagreement>> {
+        (0..view.nodes.len())
+            .map(|idx| view.disagreement(idx))
+            .collect()
+    }
+
+    #[test]
+    fn test_stable_cluster() {
+        let view = view(vec![
+            node("Leader", 4),
+            node("Follower", 4),
+            node("follower", 4),
+        ]);
+        assert_eq!(flags(&view), [None, None, None]);
+        assert_eq!(view.summary(), "Nodes: 3/3 Online | Consensus: Stable");
+        assert!(view.is_healthy());
+    }
+
+    #[test]
+    fn test_competing_leaders() {
+        let view = view(vec![
+            node("Leader", 5),
+            node("leader", 4),
+            node("Follower", 5),
+        ]);
+        assert_eq!(
+            flags(&view),
+            [
+                Some(Disagreement::CompetingLeader),
+                Some(Disagreement::CompetingLeader),
+                None
+            ]
+        );
+        assert_eq!(view.summary(), "Nodes: 3/3 Online | SPLIT BRAIN: 2 leaders");
+        assert!(!view.is_healthy());
+    }
+
+    #[test]
+    fn test_stale_term() {
+        let view = view(vec![node("Leader", 5), node("Follower", 3)]);
+        assert_eq!(flags(&view), [None, Some(Disagreement::StaleTerm)]);
+        assert_eq!(view.summary(), "Nodes: 2/2 Online | Terms diverge");
+        assert!(!view.is_healthy());
+    }
+
+    #[test]
+    fn test_unreachable_nodes_are_ignored() {
+        let view = view(vec![
+            node("Leader", 5),
+            unreachable(node("Leader", 9)),
+            unreachable(node("Follower", 1)),
+        ]);
+        assert_eq!(view.leaders(), [0]);
+        assert_eq!(view.highest_term(), Some(5));
+        assert_eq!(flags(&view), [None, None, None]);
+        assert_eq!(view.summary(), "Nodes: 1/3 Online | Consensus: Stable");
+    }
+
+    #[test]
+    fn test_no_leader() {
+        let view = view(vec![node("Candidate", 2), node("Follower", 2)]);
+        assert_eq!(view.summary(), "Nodes: 2/2 Online | No leader");
+        assert!(!view.is_healthy());
+        assert_eq!(
+            ClusterView::default().summary(),
+            "Nodes: 0/0 Online | No leader"
+        );
+    }
+}
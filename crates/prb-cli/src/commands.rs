@@ -0,0 +1,256 @@
+//! Non-interactive commands that query the control plane once and exit.
+//!
+//! Exit codes: `0` healthy, `1` deadlocks detected, `2` node unreachable or RPC failure.
+
+use crate::OneShotArgs;
+use crate::client::{ControlPlane, NodeStatus};
+use crate::inspect::{self, ResourceReport};
+use crate::timeline::Incident;
+use serde::Serialize;
+use std::process::ExitCode;
+use std::time::Duration;
+
+pub const EXIT_HEALTHY: u8 = 0;
+pub const EXIT_DEADLOCKS: u8 = 1;
+pub const EXIT_UNREACHABLE: u8 = 2;
+
+#[derive(Debug, Clone)]
+pub enum Query {
+    Status,
+    Deadlocks,
+    Logs { limit: u32 },
+    Resource { resource_id: String },
+}
+
+/// Result of a one-shot query, serialized as-is for `--json`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Report {
+    Status {
+        status: NodeStatus,
+        deadlock_count: usize,
+    },
+    Deadlocks {
+        deadlocks: Vec<String>,
+    },
+    Logs {
+        logs: Vec<String>,
+    },
+    Resource(ResourceReport),
+    Error {
+        endpoint: String,
+        message: String,
+    },
+}
+
+impl Report {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Report::Status { deadlock_count, .. } if *deadlock_count > 0 => EXIT_DEADLOCKS,
+            Report::Deadlocks { deadlocks } if !deadlocks.is_empty() => EXIT_DEADLOCKS,
+            Report::Error { .. } => EXIT_UNREACHABLE,
+            _ => EXIT_HEALTHY,
+        }
+    }
+
+    /// Renders the report as a plain-text table for humans.
+    pub fn to_table(&self) -> String {
+        match self {
+            Report::Status {
+                status,
+                deadlock_count,
+            } => format!(
+                "NODE       {}\nSTATE      {}\nTERM       {}\nCOMMIT     {}\nDEADLOCKS  {}\n",
+                status.node_id,
+                status.state,
+                status.current_term,
+                status.commit_index,
+                deadlock_count
+            ),
+            Report::Deadlocks { deadlocks } if deadlocks.is_empty() => {
+                "No deadlocks detected.\n".to_string()
+            }
+            Report::Deadlocks { deadlocks } => deadlocks
+                .iter()
+                .map(|cycle| format!("DEADLOCK  {}\n", cycle))
+                .collect(),
+            Report::Logs { logs } => logs.iter().map(|line| format!("{}\n", line)).collect(),
+            Report::Resource(report) => report.render(false),
+            Report::Error { endpoint, message } => {
+                format!("error: {} unreachable: {}\n", endpoint, message)
+            }
+        }
+    }
+}
+
+/// Runs a single query against an already-connected control plane.
+pub async fn fetch(plane: &mut ControlPlane, query: Query) -> Result<Report, tonic::Status> {
+    Ok(match query {
+        Query::Status => {
+            let status = plane.status().await?;
+            let deadlock_count = plane.deadlocks().await?.len();
+            Report::Status {
+                status,
+                deadlock_count,
+            }
+        }
+        Query::Deadlocks => Report::Deadlocks {
+            deadlocks: plane.deadlocks().await?,
+        },
+        Query::Logs { limit } => Report::Logs {
+            logs: plane.recent_logs(limit).await?,
+        },
+        Query::Resource { resource_id } => {
+            let deadlocks = plane
+                .deadlocks()
+                .await?
+                .into_iter()
+                .map(|cycle| Incident::detected(cycle, inspect::now_ms()))
+                .collect();
+            Report::Resource(inspect::inspect(plane, &resource_id, deadlocks).await?)
+        }
+    })
+}
+
+/// Connects, runs `query`, prints the report and returns the process exit code.
+///
+/// `color` only affects reports with colorized output, currently `resource`.
+pub async fn run(args: OneShotArgs, query: Query, color: bool, rpc_timeout: Duration) -> ExitCode {
+    let address = args.address.as_str();
+    let report = match ControlPlane::connect(address, rpc_timeout).await {
+        Ok(mut plane) => fetch(&mut plane, query)
+            .await
+            .unwrap_or_else(|status| Report::Error {
+                endpoint: address.to_string(),
+                message: status.to_string(),
+            }),
+        Err(e) => Report::Error {
+            endpoint: address.to_string(),
+            message: e.to_string(),
+        },
+    };
+
+    if args.json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(out) => println!("{}", out),
+            Err(e) => eprintln!("failed to encode report: {}", e),
+        }
+    } else if let Report::Error { .. } = report {
+        eprint!("{}", report.to_table());
+    } else if let Report::Resource(resource) = &report {
+        print!("{}", resource.render(color));
+    } else {
+        print!("{}", report.to_table());
+    }
+
+    ExitCode::from(report.exit_code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{self, MockNode};
+    use serde_json::json;
+
+    async fn plane(node: MockNode) -> ControlPlane {
+        let address = testing::serve(node).await;
+        ControlPlane::connect(&address, Duration::from_secs(2))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_status_report() {
+        let mut plane = plane(MockNode {
+            deadlocks: vec!["a -> b -> a".to_string()],
+            ..MockNode::leader(3)
+        })
+        .await;
+        let report = fetch(&mut plane, Query::Status).await.unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "kind": "status",
+                "status": {
+                    "node_id": 3,
+                    "state": "Leader",
+                    "current_term": 7,
+                    "commit_index": 42
+                },
+                "deadlock_count": 1
+            })
+        );
+        assert_eq!(report.exit_code(), EXIT_DEADLOCKS);
+        assert_eq!(
+            report.to_table(),
+            "NODE       3\nSTATE      Leader\nTERM       7\nCOMMIT     42\nDEADLOCKS  1\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deadlocks_report() {
+        let mut plane = plane(MockNode::leader(1)).await;
+        let report = fetch(&mut plane, Query::Deadlocks).await.unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({"kind": "deadlocks", "deadlocks": []})
+        );
+        assert_eq!(report.exit_code(), EXIT_HEALTHY);
+        assert_eq!(report.to_table(), "No deadlocks detected.\n");
+    }
+
+    #[tokio::test]
+    async fn test_logs_report_honors_limit() {
+        let mut plane = plane(MockNode {
+            logs: vec!["one".into(), "two".into(), "three".into()],
+            ..MockNode::leader(1)
+        })
+        .await;
+        let report = fetch(&mut plane, Query::Logs { limit: 2 }).await.unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({"kind": "logs", "logs": ["one", "two"]})
+        );
+        assert_eq!(report.to_table(), "one\ntwo\n");
+    }
+
+    #[tokio::test]
+    async fn test_resource_report_uses_current_deadlocks() {
+        let mut plane = plane(MockNode {
+            deadlocks: vec!["db -> cache -> db".to_string()],
+            ..MockNode::leader(1)
+        })
+        .await;
+        let query = Query::Resource {
+            resource_id: "cache".into(),
+        };
+        let Report::Resource(report) = fetch(&mut plane, query).await.unwrap() else {
+            panic!("expected a resource report");
+        };
+        assert_eq!(report.events.len(), 1);
+        assert_eq!(report.events[0].summary, "db -> cache -> db detected");
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_node() {
+        let address = testing::dead_address();
+        assert!(
+            ControlPlane::connect(&address, Duration::from_millis(500))
+                .await
+                .is_err()
+        );
+
+        let report = Report::Error {
+            endpoint: address.clone(),
+            message: "connection refused".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({"kind": "error", "endpoint": address, "message": "connection refused"})
+        );
+        assert_eq!(report.exit_code(), EXIT_UNREACHABLE);
+    }
+}
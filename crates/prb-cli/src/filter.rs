@@ -0,0 +1,195 @@
+//! Log Explorer filter syntax.
+//!
+//! - plain text: case-insensitive substring
+//! - `re:<pattern>`: regular expression
+//! - `lvl:<op><level>`: level comparison, e.g. `lvl:>=warn`
+//!
+//! Filters are compiled once when the input changes, never per frame.
+
+use regex::Regex;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "trace" => Some(Level::Trace),
+            "debug" => Some(Level::Debug),
+            "info" => Some(Level::Info),
+            "warn" | "warning" => Some(Level::Warn),
+            "error" | "err" => Some(Level::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Level of a log line: the first word that names a level, e.g. `ERROR` or `[warn]`.
+pub fn level_of(line: &str) -> Option<Level> {
+    line.split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty())
+        .find_map(Level::parse)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+impl Comparison {
+    fn holds(self, actual: Level, expected: Level) -> bool {
+        match self {
+            Comparison::Lt => actual < expected,
+            Comparison::Le => actual <= expected,
+            Comparison::Eq => actual == expected,
+            Comparison::Ge => actual >= expected,
+            Comparison::Gt => actual > expected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum LogFilter {
+    #[default]
+    All,
+    /// Substring and `re:` filters, both compiled to a regex for matching and highlighting.
+    Pattern(Regex),
+    Level(Comparison, Level),
+}
+
+impl LogFilter {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        if input.is_empty() {
+            return Ok(LogFilter::All);
+        }
+        if let Some(pattern) = input.strip_prefix("re:") {
+            return Regex::new(pattern)
+                .map(LogFilter::Pattern)
+                .map_err(|e| format!("invalid regex: {}", e));
+        }
+        if let Some(spec) = input.strip_prefix("lvl:") {
+            let (op, rest) = [
+                (">=", Comparison::Ge),
+                ("<=", Comparison::Le),
+                (">", Comparison::Gt),
+                ("<", Comparison::Lt),
+                ("=", Comparison::Eq),
+            ]
+            .into_iter()
+            .find_map(|(token, op)| spec.strip_prefix(token).map(|rest| (op, rest)))
+            .unwrap_or((Comparison::Eq, spec));
+            let level = Level::parse(rest.trim())
+                .ok_or_else(|| format!("unknown level `{}`", rest.trim()))?;
+            return Ok(LogFilter::Level(op, level));
+        }
+        Regex::new(&format!("(?i){}", regex::escape(input)))
+            .map(LogFilter::Pattern)
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::Pattern(re) => re.is_match(line),
+            LogFilter::Level(op, level) => level_of(line).is_some_and(|l| op.holds(l, *level)),
+        }
+    }
+
+    /// Byte ranges of `line` to highlight.
+    pub fn highlights(&self, line: &str) -> Vec<Range<usize>> {
+        match self {
+            LogFilter::Pattern(re) => re
+                .find_iter(line)
+                .filter(|m| !m.is_empty())
+                .map(|m| m.range())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matching<'a>(input: &str, lines: &[&'a str]) -> Vec<&'a str> {
+        let filter = LogFilter::parse(input).unwrap();
+        lines
+            .iter()
+            .copied()
+            .filter(|line| filter.matches(line))
+            .collect()
+    }
+
+    const LINES: [&str; 4] = [
+        "INFO granted lease on db",
+        "[warn] lease on cache expiring",
+        "ERROR deadlock a -> b",
+        "heartbeat ok",
+    ];
+
+    #[test]
+    fn test_empty_input_matches_everything() {
+        assert!(matches!(LogFilter::parse("").unwrap(), LogFilter::All));
+        assert_eq!(matching("", &LINES), LINES);
+    }
+
+    #[test]
+    fn test_substring_is_case_insensitive_and_literal() {
+        assert_eq!(matching("LEASE", &LINES), &LINES[..2]);
+        assert_eq!(matching("a -> b", &LINES), ["ERROR deadlock a -> b"]);
+        assert!(matching("(", &LINES).is_empty());
+    }
+
+    #[test]
+    fn test_regex() {
+        assert_eq!(
+            matching("re:^(INFO|ERROR)", &LINES),
+            ["INFO granted lease on db", "ERROR deadlock a -> b"]
+        );
+        let err = LogFilter::parse("re:(").unwrap_err();
+        assert!(err.starts_with("invalid regex"));
+    }
+
+    #[test]
+    fn test_level_comparisons() {
+        assert_eq!(matching("lvl:>=warn", &LINES), &LINES[1..3]);
+        assert_eq!(matching("lvl:<warn", &LINES), ["INFO granted lease on db"]);
+        assert_eq!(matching("lvl:error", &LINES), ["ERROR deadlock a -> b"]);
+        assert_eq!(matching("lvl:= info", &LINES), ["INFO granted lease on db"]);
+        assert_eq!(
+            LogFilter::parse("lvl:>loud").unwrap_err(),
+            "unknown level `loud`"
+        );
+    }
+
+    #[test]
+    fn test_level_of() {
+        assert_eq!(level_of("2024-01-01 WARNING disk"), Some(Level::Warn));
+        assert_eq!(level_of("[err] boom"), Some(Level::Error));
+        assert_eq!(level_of("heartbeat ok"), None);
+    }
+
+    #[test]
+    fn test_highlights() {
+        let filter = LogFilter::parse("lease").unwrap();
+        assert_eq!(filter.highlights("Lease a, lease b"), [0..5, 9..14]);
+        assert!(
+            LogFilter::parse("lvl:warn")
+                .unwrap()
+                .highlights("WARN lease")
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,294 @@
+//! Wait-for graph layout for the Deadlocks tab.
+//!
+//! The layout is pure: it turns cycles (and optional extra wait edges) into lines of
+//! box-drawing text tagged with whether each segment belongs to a cycle. Rendering
+//! only has to color the segments.
+
+use std::collections::{BTreeSet, HashSet};
+
+/// Node count above which the graph is reduced to cycle nodes plus one hop.
+pub const MAX_FULL_GRAPH_NODES: usize = 24;
+
+/// Parses a cycle description such as `1 -> 200 -> 2 -> 1` into its nodes.
+///
+/// The closing repetition of the first node is dropped.
+pub fn parse_cycle(description: &str) -> Vec<String> {
+    let mut nodes: Vec<String> = description
+        .split("->")
+        .map(|node| node.trim().to_string())
+        .filter(|node| !node.is_empty())
+        .collect();
+    if nodes.len() > 1 && nodes.first() == nodes.last() {
+        nodes.pop();
+    }
+    nodes
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WaitGraph {
+    pub cycles: Vec<Vec<String>>,
+    /// Wait edges `(waiter, awaited)` that are not part of any cycle.
+    pub edges: Vec<(String, String)>,
+}
+
+impl WaitGraph {
+    pub fn from_cycle_descriptions<'a>(descriptions: impl IntoIterator<Item = &'a String>) -> Self {
+        Self {
+            cycles: descriptions
+                .into_iter()
+                .map(|d| parse_cycle(d))
+                .filter(|c| !c.is_empty())
+                .collect(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        let mut nodes: HashSet<&str> = HashSet::new();
+        for cycle in &self.cycles {
+            nodes.extend(cycle.iter().map(String::as_str));
+        }
+        for (from, to) in &self.edges {
+            nodes.insert(from);
+            nodes.insert(to);
+        }
+        nodes.len()
+    }
+
+    /// Keeps cycles plus edges touching a cycle node, dropping the rest of the graph.
+    pub fn focused(&self) -> Self {
+        let in_cycle: HashSet<&str> = self
+            .cycles
+            .iter()
+            .flat_map(|c| c.iter().map(String::as_str))
+            .collect();
+        Self {
+            cycles: self.cycles.clone(),
+            edges: self
+                .edges
+                .iter()
+                .filter(|(from, to)| {
+                    in_cycle.contains(from.as_str()) || in_cycle.contains(to.as_str())
+                })
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// A run of text in the diagram; `in_cycle` segments are highlighted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub text: String,
+    pub in_cycle: bool,
+}
+
+pub type DiagramLine = Vec<Segment>;
+
+fn seg(text: impl Into<String>, in_cycle: bool) -> Segment {
+    Segment {
+        text: text.into(),
+        in_cycle,
+    }
+}
+
+/// Lays out the graph within `width` columns.
+///
+/// Each cycle is drawn as a row of boxes joined by arrows with a return edge
+/// underneath; cycles too wide for the area fall back to a single arrow line.
+/// Non-cycle edges follow as `a ──▶ b` lines.
+pub fn layout(graph: &WaitGraph, width: usize) -> Vec<DiagramLine> {
+    let graph = if graph.node_count() > MAX_FULL_GRAPH_NODES {
+        graph.focused()
+    } else {
+        graph.clone()
+    };
+
+    let mut lines = Vec::new();
+    for (i, cycle) in graph.cycles.iter().enumerate() {
+        if i > 0 {
+            lines.push(Vec::new());
+        }
+        lines.push(vec![seg(
+            format!("Cycle {} ({} nodes)", i + 1, cycle.len()),
+            false,
+        )]);
+        if boxed_width(cycle) <= width {
+            lines.extend(boxed_cycle(cycle));
+        } else {
+            lines.push(compact_cycle(cycle));
+        }
+    }
+
+    // Deterministic order for context edges
+    let context: BTreeSet<&(String, String)> = graph.edges.iter().collect();
+    if !context.is_empty() {
+        lines.push(Vec::new());
+        lines.push(vec![seg("Context", false)]);
+        for (from, to) in context {
+            lines.push(vec![seg(format!("{} ──▶ {}", from, to), false)]);
+        }
+    }
+    lines
+}
+
+const ARROW: &str = "──▶";
+
+fn box_width(label: &str) -> usize {
+    label.chars().count() + 4
+}
+
+fn boxed_width(cycle: &[String]) -> usize {
+    let boxes: usize = cycle.iter().map(|n| box_width(n)).sum();
+    boxes + ARROW.chars().count() * cycle.len().saturating_sub(1)
+}
+
+fn boxed_cycle(cycle: &[String]) -> Vec<DiagramLine> {
+    let gap = " ".repeat(ARROW.chars().count());
+    let (mut top, mut mid, mut bottom) = (String::new(), String::new(), String::new());
+    let last = cycle.len() - 1;
+
+    for (i, node) in cycle.iter().enumerate() {
+        let inner = node.chars().count() + 2;
+        top.push_str(&format!("┌{}┐", "─".repeat(inner)));
+        mid.push_str(&format!("│ {} │", node));
+        if i == last && last > 0 {
+            let left = inner / 2;
+            bottom.push_str(&format!(
+                "└{}┬{}┘",
+                "─".repeat(left),
+                "─".repeat(inner - left - 1)
+            ));
+        } else {
+            bottom.push_str(&format!("└{}┘", "─".repeat(inner)));
+        }
+        if i < last {
+            top.push_str(&gap);
+            mid.push_str(ARROW);
+            bottom.push_str(&gap);
+        }
+    }
+
+    // Return edge from the last node back into the first
+    let first_center = box_width(&cycle[0]) / 2;
+    let total = boxed_width(cycle);
+    let last_center = total - box_width(&cycle[last]) + 1 + (cycle[last].chars().count() + 2) / 2;
+    let (up, back) = if last == 0 {
+        // Self-wait: a single node waiting on itself
+        (
+            format!("{}▲", " ".repeat(first_center)),
+            format!("{}└─┘", " ".repeat(first_center)),
+        )
+    } else {
+        (
+            format!(
+                "{}▲{}│",
+                " ".repeat(first_center),
+                " ".repeat(last_center - first_center - 1)
+            ),
+            format!(
+                "{}└{}┘",
+                " ".repeat(first_center),
+                "─".repeat(last_center - first_center - 1)
+            ),
+        )
+    };
+
+    [top, mid, bottom, up, back]
+        .into_iter()
+        .map(|text| vec![seg(text, true)])
+        .collect()
+}
+
+fn compact_cycle(cycle: &[String]) -> DiagramLine {
+    let mut text = cycle.join(" → ");
+    text.push_str(" → ↺");
+    vec![seg(text, true)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::strings;
+
+    fn text(lines: &[DiagramLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.iter().map(|s| s.text.as_str()).collect())
+            .collect()
+    }
+
+    fn graph(descriptions: &[&str]) -> WaitGraph {
+        WaitGraph::from_cycle_descriptions(&strings(descriptions))
+    }
+
+    #[test]
+    fn test_parse_cycle() {
+        assert_eq!(parse_cycle("1 -> 200 -> 2 -> 1"), ["1", "200", "2"]);
+        assert_eq!(parse_cycle("a -> b"), ["a", "b"]);
+        assert_eq!(parse_cycle("a"), ["a"]);
+        assert!(parse_cycle(" -> ").is_empty());
+    }
+
+    #[test]
+    fn test_single_cycle() {
+        let lines = layout(&graph(&["a -> b -> a"]), 80);
+        assert_eq!(
+            text(&lines),
+            [
+                "Cycle 1 (2 nodes)",
+                "┌───┐   ┌───┐",
+                "│ a │──▶│ b │",
+                "└───┘   └─┬─┘",
+                "  ▲       │",
+                "  └───────┘",
+            ]
+        );
+        assert!(!lines[0][0].in_cycle);
+        assert!(lines[1..].iter().all(|line| line[0].in_cycle));
+    }
+
+    #[test]
+    fn test_two_disjoint_cycles() {
+        let lines = text(&layout(&graph(&["a -> b -> a", "x -> y -> z -> x"]), 80));
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "Cycle 1 (2 nodes)");
+        assert_eq!(lines[6], "");
+        assert_eq!(lines[7], "Cycle 2 (3 nodes)");
+        assert_eq!(lines[9], "│ x │──▶│ y │──▶│ z │");
+    }
+
+    #[test]
+    fn test_narrow_area_falls_back_to_compact() {
+        let lines = text(&layout(&graph(&["a -> b -> a"]), 10));
+        assert_eq!(lines, ["Cycle 1 (2 nodes)", "a → b → ↺"]);
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        assert!(layout(&WaitGraph::default(), 80).is_empty());
+
+        let only_edges = WaitGraph {
+            cycles: Vec::new(),
+            edges: vec![("b".into(), "c".into()), ("a".into(), "b".into())],
+        };
+        assert_eq!(
+            text(&layout(&only_edges, 80)),
+            ["", "Context", "a ──▶ b", "b ──▶ c"]
+        );
+    }
+
+    #[test]
+    fn test_large_graph_keeps_edges_near_cycles() {
+        let mut graph = graph(&["a -> b -> a"]);
+        graph.edges.push(("c".into(), "a".into()));
+        graph
+            .edges
+            .extend((0..MAX_FULL_GRAPH_NODES).map(|i| (format!("n{}", i), format!("m{}", i))));
+        assert!(graph.node_count() > MAX_FULL_GRAPH_NODES);
+
+        let lines = text(&layout(&graph, 80));
+        assert_eq!(lines.last().unwrap(), "c ──▶ a");
+        assert!(!lines.iter().any(|line| line.starts_with('n')));
+    }
+}
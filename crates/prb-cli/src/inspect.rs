@@ -0,0 +1,266 @@
+//! Everything known about one resource, merged into a single chronological account.
+//!
+//! Backs the `resource <id>` command and the `i` key in the Deadlocks tab.
+
+use crate::client::ControlPlane;
+use crate::timeline::Incident;
+use crossterm::style::Stylize;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Lines requested from `GetRecentLogs`; the server caps it at 100.
+pub const LOG_LIMIT: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceEvent {
+    /// Unix time in milliseconds.
+    pub at_ms: u64,
+    pub summary: String,
+}
+
+/// What the caller knows that may concern one resource.
+#[derive(Debug, Default)]
+pub struct Responses {
+    /// Deadlock incidents observed by the caller, since `GetDeadlocks` only reports the
+    /// cycles that exist right now.
+    pub deadlocks: Vec<Incident>,
+    /// Recent log lines, newest first.
+    pub logs: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceReport {
+    pub resource_id: String,
+    pub generated_at_ms: u64,
+    /// Deadlocks the resource took part in, oldest first.
+    pub events: Vec<ResourceEvent>,
+    /// Recent log lines naming the resource, newest first. The server does not timestamp
+    /// them, so they are listed apart from `events`.
+    pub log_lines: Vec<String>,
+}
+
+impl ResourceReport {
+    /// Plain or ANSI-colored text for the `resource` command.
+    pub fn render(&self, color: bool) -> String {
+        let paint = |text: &str| {
+            if color {
+                text.red().bold().to_string()
+            } else {
+                text.to_string()
+            }
+        };
+
+        let mut out = format!("RESOURCE   {}\n\n", self.resource_id);
+        if self.events.is_empty() {
+            out.push_str("No deadlocks observed.\n");
+        }
+        for event in &self.events {
+            out.push_str(&format!(
+                "{}  {} {}\n",
+                format_utc(event.at_ms),
+                paint("DEADLOCK"),
+                event.summary
+            ));
+        }
+        out.push_str("\nRECENT LOGS\n");
+        if self.log_lines.is_empty() {
+            out.push_str("No recent log line mentions it.\n");
+        }
+        for line in &self.log_lines {
+            out.push_str(&format!("  {}\n", line));
+        }
+        out
+    }
+}
+
+/// Merges the responses into a report as of `now_ms`.
+///
+/// Deadlocks and log lines are kept only when they name the resource.
+pub fn merge(resource_id: &str, responses: Responses, now_ms: u64) -> ResourceReport {
+    let mut events = Vec::new();
+    for incident in responses
+        .deadlocks
+        .iter()
+        .filter(|incident| incident.involves(resource_id))
+    {
+        events.push(ResourceEvent {
+            at_ms: incident.first_seen_ms,
+            summary: format!("{} detected", incident.cycle),
+        });
+        if let Some(at_ms) = incident.cleared_at_ms {
+            events.push(ResourceEvent {
+                at_ms,
+                summary: format!("{} cleared", incident.cycle),
+            });
+        }
+    }
+    events.sort_by_key(|event| event.at_ms);
+
+    ResourceReport {
+        resource_id: resource_id.to_string(),
+        generated_at_ms: now_ms,
+        events,
+        log_lines: responses
+            .logs
+            .into_iter()
+            .filter(|line| mentions(line, resource_id))
+            .collect(),
+    }
+}
+
+/// Fetches the node's recent logs and merges in the `deadlocks` seen so far.
+pub async fn inspect(
+    plane: &mut ControlPlane,
+    resource_id: &str,
+    deadlocks: Vec<Incident>,
+) -> Result<ResourceReport, tonic::Status> {
+    let responses = Responses {
+        deadlocks,
+        logs: plane.recent_logs(LOG_LIMIT).await?,
+    };
+    Ok(merge(resource_id, responses, now_ms()))
+}
+
+/// Whether `line` names `resource_id` as a whole word, so `db` does not match `dbx`.
+fn mentions(line: &str, resource_id: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    !resource_id.is_empty()
+        && line.match_indices(resource_id).any(|(at, _)| {
+            let before = line[..at].chars().next_back();
+            let after = line[at + resource_id.len()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(is_word)
+        })
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Formats Unix milliseconds as `2024-05-01 12:30:05Z`.
+pub fn format_utc(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // Civil-from-days, valid for the proleptic Gregorian calendar from 1970 onwards
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_000_000;
+
+    fn responses() -> Responses {
+        Responses {
+            deadlocks: vec![
+                Incident {
+                    cleared_at_ms: Some(998_000),
+                    ..Incident::detected("db -> cache -> db".into(), 995_000)
+                },
+                Incident::detected("queue -> cache -> queue".into(), 990_000),
+            ],
+            logs: vec![
+                "WARN lock wait on queue".into(),
+                "INFO granted db to node-1".into(),
+                "INFO granted dbx to node-2".into(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_merge_orders_deadlocks() {
+        let report = merge("cache", responses(), NOW);
+        let events: Vec<(u64, &str)> = report
+            .events
+            .iter()
+            .map(|e| (e.at_ms, e.summary.as_str()))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (990_000, "queue -> cache -> queue detected"),
+                (995_000, "db -> cache -> db detected"),
+                (998_000, "db -> cache -> db cleared"),
+            ]
+        );
+        assert!(report.log_lines.is_empty());
+    }
+
+    #[test]
+    fn test_merge_keeps_only_what_names_the_resource() {
+        let report = merge("db", responses(), NOW);
+        assert_eq!(report.events.len(), 2);
+        assert_eq!(report.log_lines, ["INFO granted db to node-1"]);
+
+        let report = merge("other", responses(), NOW);
+        assert!(report.events.is_empty() && report.log_lines.is_empty());
+    }
+
+    #[test]
+    fn test_mentions_whole_words_only() {
+        assert!(mentions("lease on db: granted", "db"));
+        assert!(mentions("db", "db"));
+        assert!(!mentions("lease on dbx", "db"));
+        assert!(!mentions("lease on my-db", "db"));
+        assert!(!mentions("anything", ""));
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let report = merge("db", responses(), NOW);
+        let text = report.render(false);
+        assert!(text.starts_with("RESOURCE   db\n\n"));
+        assert!(text.contains("1970-01-01 00:16:35Z  DEADLOCK db -> cache -> db detected\n"));
+        assert!(text.ends_with("RECENT LOGS\n  INFO granted db to node-1\n"));
+        assert!(!text.contains('\u{1b}'));
+        assert!(report.render(true).contains('\u{1b}'));
+
+        let empty = merge("db", Responses::default(), NOW).render(false);
+        assert!(empty.contains("No deadlocks observed.\n"));
+        assert!(empty.contains("No recent log line mentions it.\n"));
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01 00:00:00Z");
+        assert_eq!(format_utc(1_714_566_605_000), "2024-05-01 12:30:05Z");
+        assert_eq!(format_utc(951_782_400_000), "2000-02-29 00:00:00Z");
+    }
+
+    #[tokio::test]
+    async fn test_inspect_reads_recent_logs() {
+        use crate::testing::{self, MockNode};
+        use std::time::Duration;
+
+        let address = testing::serve(MockNode {
+            logs: vec!["INFO granted db".into(), "INFO granted cache".into()],
+            ..MockNode::leader(1)
+        })
+        .await;
+        let mut plane = ControlPlane::connect(&address, Duration::from_secs(2))
+            .await
+            .unwrap();
+        let report = inspect(&mut plane, "db", Vec::new()).await.unwrap();
+        assert_eq!(report.log_lines, ["INFO granted db"]);
+        assert!(report.events.is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+//! Declarative key bindings, including multi-key chords such as `gg`.
+
+use crate::app::{DEADLOCKS_TAB, LOGS_TAB, TAB_TITLES};
+use clap::ValueEnum;
+use crossterm::event::KeyCode;
+
+/// `--keymap` presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum KeymapStyle {
+    #[default]
+    Default,
+    /// Adds `h`/`l` for tabs, `j`/`k` for rows and `gg`/`G` for top/bottom.
+    Vim,
+}
+
+/// What a key sequence does; `run_app` maps these onto `App`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    Quit,
+    Help,
+    NextTab,
+    PrevTab,
+    Up,
+    Down,
+    Top,
+    Bottom,
+    PageUp,
+    PageDown,
+    ToggleFollow,
+    CopyLine,
+    Pause,
+    Inspect,
+    StartFilter,
+    ClearFilter,
+    ScrubBack,
+    ScrubForward,
+    TogglePlay,
+    Faster,
+    Slower,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    pub keys: &'static [KeyCode],
+    /// Tab the binding is limited to; `None` applies everywhere.
+    pub tab: Option<usize>,
+    pub action: KeyAction,
+    pub help: &'static str,
+}
+
+const fn bind(keys: &'static [KeyCode], action: KeyAction, help: &'static str) -> Binding {
+    Binding {
+        keys,
+        tab: None,
+        action,
+        help,
+    }
+}
+
+const fn bind_in(
+    tab: usize,
+    keys: &'static [KeyCode],
+    action: KeyAction,
+    help: &'static str,
+) -> Binding {
+    Binding {
+        keys,
+        tab: Some(tab),
+        action,
+        help,
+    }
+}
+
+const DEFAULT_BINDINGS: &[Binding] = &[
+    bind(&[KeyCode::Char('q')], KeyAction::Quit, "Quit"),
+    bind(&[KeyCode::Char('?')], KeyAction::Help, "Show this help"),
+    bind(&[KeyCode::Tab], KeyAction::NextTab, "Next tab"),
+    bind(&[KeyCode::BackTab], KeyAction::PrevTab, "Previous tab"),
+    bind(&[KeyCode::Up], KeyAction::Up, "Move selection up"),
+    bind(&[KeyCode::Down], KeyAction::Down, "Move selection down"),
+    bind(&[KeyCode::Home], KeyAction::Top, "Jump to first row"),
+    bind(
+        &[KeyCode::Char('/')],
+        KeyAction::StartFilter,
+        "Filter (regex or level>=warn)",
+    ),
+    bind(&[KeyCode::Esc], KeyAction::ClearFilter, "Clear filter"),
+    bind(
+        &[KeyCode::Char('p')],
+        KeyAction::Pause,
+        "Pause / resume the view",
+    ),
+    bind(
+        &[KeyCode::Char('[')],
+        KeyAction::ScrubBack,
+        "Offline: step back in time",
+    ),
+    bind(
+        &[KeyCode::Char(']')],
+        KeyAction::ScrubForward,
+        "Offline: step forward in time",
+    ),
+    bind(
+        &[KeyCode::Char(' ')],
+        KeyAction::TogglePlay,
+        "Replay: play / pause",
+    ),
+    bind(&[KeyCode::Char('+')], KeyAction::Faster, "Replay: faster"),
+    bind(&[KeyCode::Char('=')], KeyAction::Faster, "Replay: faster"),
+    bind(&[KeyCode::Char('-')], KeyAction::Slower, "Replay: slower"),
+    bind_in(LOGS_TAB, &[KeyCode::PageUp], KeyAction::PageUp, "Page up"),
+    bind_in(
+        LOGS_TAB,
+        &[KeyCode::PageDown],
+        KeyAction::PageDown,
+        "Page down",
+    ),
+    bind_in(
+        LOGS_TAB,
+        &[KeyCode::End],
+        KeyAction::ToggleFollow,
+        "Toggle follow",
+    ),
+    bind_in(
+        LOGS_TAB,
+        &[KeyCode::Char('F')],
+        KeyAction::ToggleFollow,
+        "Toggle follow",
+    ),
+    bind_in(
+        LOGS_TAB,
+        &[KeyCode::Char('y')],
+        KeyAction::CopyLine,
+        "Copy selected line",
+    ),
+    bind_in(
+        DEADLOCKS_TAB,
+        &[KeyCode::Char('i')],
+        KeyAction::Inspect,
+        "Inspect the cycle's first resource",
+    ),
+];
+
+const VIM_BINDINGS: &[Binding] = &[
+    bind(&[KeyCode::Char('h')], KeyAction::PrevTab, "Previous tab"),
+    bind(&[KeyCode::Char('l')], KeyAction::NextTab, "Next tab"),
+    bind(&[KeyCode::Char('k')], KeyAction::Up, "Move selection up"),
+    bind(
+        &[KeyCode::Char('j')],
+        KeyAction::Down,
+        "Move selection down",
+    ),
+    bind(
+        &[KeyCode::Char('g'), KeyCode::Char('g')],
+        KeyAction::Top,
+        "Jump to first row",
+    ),
+    bind(&[KeyCode::Char('G')], KeyAction::Bottom, "Jump to last row"),
+];
+
+/// Resolves key presses against the active bindings, buffering chord prefixes.
+#[derive(Debug)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+    pending: Vec<KeyCode>,
+}
+
+impl Keymap {
+    pub fn new(style: KeymapStyle) -> Self {
+        let mut bindings = DEFAULT_BINDINGS.to_vec();
+        if style == KeymapStyle::Vim {
+            bindings.extend_from_slice(VIM_BINDINGS);
+        }
+        Self {
+            bindings,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// Feeds one key; returns the action once a full sequence matched on `tab`.
+    ///
+    /// A key that extends no binding drops the pending prefix and is retried on its own,
+    /// so `g` followed by `j` still moves down.
+    pub fn resolve(&mut self, key: KeyCode, tab: usize) -> Option<KeyAction> {
+        self.pending.push(key);
+        let active = |binding: &&Binding| binding.tab.is_none_or(|t| t == tab);
+
+        if let Some(binding) = self
+            .bindings
+            .iter()
+            .filter(active)
+            .find(|binding| binding.keys == self.pending.as_slice())
+        {
+            self.pending.clear();
+            return Some(binding.action);
+        }
+        let is_prefix = self.bindings.iter().filter(active).any(|binding| {
+            binding.keys.len() > self.pending.len() && binding.keys.starts_with(&self.pending)
+        });
+        if is_prefix {
+            return None;
+        }
+
+        let retry = self.pending.len() > 1;
+        self.pending.clear();
+        if retry { self.resolve(key, tab) } else { None }
+    }
+}
+
+/// Section title for a binding in the help overlay.
+pub fn scope_label(tab: Option<usize>) -> &'static str {
+    match tab {
+        None => "All tabs",
+        Some(tab) => TAB_TITLES.get(tab).copied().unwrap_or("Tab"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OVERVIEW_TAB: usize = 0;
+
+    fn press(keymap: &mut Keymap, keys: &str, tab: usize) -> Vec<Option<KeyAction>> {
+        keys.chars()
+            .map(|c| keymap.resolve(KeyCode::Char(c), tab))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_keys() {
+        let mut keymap = Keymap::new(KeymapStyle::Default);
+        assert_eq!(
+            keymap.resolve(KeyCode::Char('q'), OVERVIEW_TAB),
+            Some(KeyAction::Quit)
+        );
+        assert_eq!(
+            keymap.resolve(KeyCode::Tab, LOGS_TAB),
+            Some(KeyAction::NextTab)
+        );
+        assert_eq!(keymap.resolve(KeyCode::Char('x'), OVERVIEW_TAB), None);
+    }
+
+    #[test]
+    fn test_gg_chord() {
+        let mut keymap = Keymap::new(KeymapStyle::Vim);
+        assert_eq!(
+            press(&mut keymap, "gg", LOGS_TAB),
+            [None, Some(KeyAction::Top)]
+        );
+        // The chord is consumed, so a third `g` starts a new one
+        assert_eq!(press(&mut keymap, "g", LOGS_TAB), [None]);
+        assert_eq!(press(&mut keymap, "g", LOGS_TAB), [Some(KeyAction::Top)]);
+    }
+
+    #[test]
+    fn test_broken_chord_retries_key() {
+        let mut keymap = Keymap::new(KeymapStyle::Vim);
+        assert_eq!(
+            press(&mut keymap, "gj", LOGS_TAB),
+            [None, Some(KeyAction::Down)]
+        );
+        assert_eq!(press(&mut keymap, "gx", LOGS_TAB), [None, None]);
+        assert_eq!(press(&mut keymap, "G", LOGS_TAB), [Some(KeyAction::Bottom)]);
+    }
+
+    #[test]
+    fn test_default_style_has_no_vim_keys() {
+        let mut keymap = Keymap::new(KeymapStyle::Default);
+        assert_eq!(
+            press(&mut keymap, "ggjk", LOGS_TAB),
+            [None, None, None, None]
+        );
+        assert!(keymap.bindings().len() < Keymap::new(KeymapStyle::Vim).bindings().len());
+    }
+
+    #[test]
+    fn test_tab_scoped_bindings() {
+        let mut keymap = Keymap::new(KeymapStyle::Default);
+        assert_eq!(
+            press(&mut keymap, "y", LOGS_TAB),
+            [Some(KeyAction::CopyLine)]
+        );
+        assert_eq!(press(&mut keymap, "y", DEADLOCKS_TAB), [None]);
+        assert_eq!(
+            press(&mut keymap, "i", DEADLOCKS_TAB),
+            [Some(KeyAction::Inspect)]
+        );
+        assert_eq!(press(&mut keymap, "i", LOGS_TAB), [None]);
+    }
+
+    #[test]
+    fn test_scope_label() {
+        assert_eq!(scope_label(None), "All tabs");
+        assert_eq!(scope_label(Some(DEADLOCKS_TAB)), "Deadlocks");
+        assert_eq!(scope_label(Some(99)), "Tab");
+    }
+}
@@ -0,0 +1,165 @@
+//! Scrollback and selection state for the Log Explorer.
+//!
+//! Logs are displayed newest first, so index `0` is the newest visible line.
+//! In follow mode the selection sticks to it; any manual movement pauses follow
+//! until it is re-enabled with `End`/`F`.
+
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogView {
+    cursor: usize,
+    follow: bool,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self {
+            cursor: 0,
+            follow: true,
+        }
+    }
+}
+
+impl LogView {
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    /// Selected row within a visible list of `len` lines, if any.
+    pub fn selected(&self, len: usize) -> Option<usize> {
+        if len == 0 {
+            None
+        } else if self.follow {
+            Some(0)
+        } else {
+            Some(self.cursor.min(len - 1))
+        }
+    }
+
+    /// Moves the selection by `delta` rows (negative is towards newer lines).
+    pub fn scroll(&mut self, delta: isize, len: usize) {
+        let current = self.selected(len).unwrap_or(0);
+        self.follow = false;
+        self.cursor = current
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    pub fn page_up(&mut self, page: usize, len: usize) {
+        self.scroll(-(page.max(1) as isize), len);
+    }
+
+    pub fn page_down(&mut self, page: usize, len: usize) {
+        self.scroll(page.max(1) as isize, len);
+    }
+
+    pub fn toggle_follow(&mut self) {
+        self.follow = !self.follow;
+        if self.follow {
+            self.cursor = 0;
+        }
+    }
+
+    /// Keeps the same line selected when `count` newer lines are inserted on top.
+    pub fn on_prepended(&mut self, count: usize) {
+        if !self.follow {
+            self.cursor = self.cursor.saturating_add(count);
+        }
+    }
+}
+
+/// Copies a log line to `export` if given, otherwise to the system clipboard.
+///
+/// Returns a short description of where the line went.
+pub fn export_line(line: &str, export: Option<&Path>) -> Result<String, String> {
+    if let Some(path) = export {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("cannot open {:?}: {}", path, e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("cannot write {:?}: {}", path, e))?;
+        return Ok(format!("Appended line to {:?}", path));
+    }
+    copy_to_clipboard(line)
+}
+
+#[cfg(feature = "clipboard")]
+fn copy_to_clipboard(line: &str) -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(line.to_string()))
+        .map(|()| "Copied line to clipboard".to_string())
+        .map_err(|e| format!("clipboard unavailable: {}", e))
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn copy_to_clipboard(_line: &str) -> Result<String, String> {
+    Err("built without clipboard support; pass --log-export <file>".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_list_has_no_selection() {
+        let mut view = LogView::default();
+        assert_eq!(view.selected(0), None);
+        view.scroll(3, 0);
+        assert_eq!(view.selected(0), None);
+        assert_eq!(view.selected(5), Some(0));
+    }
+
+    #[test]
+    fn test_scroll_clamps_at_both_ends() {
+        let mut view = LogView::default();
+        view.scroll(-1, 5);
+        assert!(!view.is_following());
+        assert_eq!(view.selected(5), Some(0));
+        view.scroll(100, 5);
+        assert_eq!(view.selected(5), Some(4));
+        view.page_up(2, 5);
+        assert_eq!(view.selected(5), Some(2));
+        view.page_down(0, 5);
+        assert_eq!(view.selected(5), Some(3));
+        // The list shrinking under the cursor keeps it on the last line
+        assert_eq!(view.selected(2), Some(1));
+    }
+
+    #[test]
+    fn test_prepend_keeps_selected_line() {
+        let mut view = LogView::default();
+        view.on_prepended(3);
+        assert_eq!(view.selected(10), Some(0));
+
+        view.scroll(2, 10);
+        view.on_prepended(3);
+        assert_eq!(view.selected(13), Some(5));
+    }
+
+    #[test]
+    fn test_toggle_follow_jumps_to_newest() {
+        let mut view = LogView::default();
+        view.scroll(4, 10);
+        view.toggle_follow();
+        assert!(view.is_following());
+        assert_eq!(view.selected(10), Some(0));
+        view.toggle_follow();
+        assert_eq!(view.selected(10), Some(0));
+    }
+
+    #[test]
+    fn test_export_appends_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.log");
+        export_line("first", Some(&path)).unwrap();
+        let message = export_line("second", Some(&path)).unwrap();
+        assert!(message.starts_with("Appended line to"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+
+        let missing = dir.path().join("missing").join("export.log");
+        assert!(export_line("line", Some(&missing)).is_err());
+    }
+}
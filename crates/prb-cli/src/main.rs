@@ -1,460 +1,437 @@
-use clap::{Parser, Subcommand};
+// `tonic::Status` is returned unboxed throughout, matching the generated clients
+#![allow(clippy::result_large_err)]
+
+use clap::{Args, Parser, Subcommand};
 use crossterm::{
+    cursor::Show,
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use ratatui::{
-    Terminal,
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
+use ratatui::{Terminal, backend::CrosstermBackend};
+use std::{
+    error::Error,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, Instant},
 };
-use std::{collections::VecDeque, error::Error, io, path::PathBuf, time::Duration};
-
-const HEARTBEAT_TICK_RATE: u64 = 10;
-const DEADLOCK_CHECK_TICK_RATE: u64 = 50;
+use tokio::sync::mpsc;
+
+mod alerts;
+mod app;
+mod client;
+mod cluster;
+mod commands;
+mod filter;
+mod graph;
+mod inspect;
+mod keymap;
+mod logview;
+mod recording;
+mod snapshot;
+mod source;
+#[cfg(test)]
+mod testing;
+mod theme;
+mod timeline;
+mod ui;
+
+use alerts::{ALERT_DEBOUNCE, Alert, AlertEngine, WebhookSender};
+use app::App;
+use commands::Query;
+use keymap::{KeyAction, KeymapStyle};
+use recording::Recorder;
+use snapshot::Snapshot;
+use source::{DataSource, Idle, LiveSource, ReplaySource, Sample};
+use theme::{Theme, ThemeName};
+use ui::ui;
+
+const MIN_POLL_INTERVAL_MS: u64 = 200;
+const MIN_DEADLOCK_INTERVAL_MS: u64 = 500;
+const MIN_RPC_TIMEOUT_MS: u64 = 100;
+/// Redraw cadence when nothing changed, so the Timeline axis keeps moving.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+const DEFAULT_ADDRESS: &str = "http://127.0.0.1:50051";
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
-    mode: Mode,
+    command: Command,
+    /// Append lines copied with 'y' in the Log Explorer to this file instead of the clipboard
+    #[arg(long, global = true)]
+    log_export: Option<PathBuf>,
+    /// Milliseconds between status and log polls
+    #[arg(long, global = true, default_value_t = 1000)]
+    #[arg(value_parser = clap::value_parser!(u64).range(MIN_POLL_INTERVAL_MS..))]
+    poll_interval: u64,
+    /// Milliseconds between deadlock checks
+    #[arg(long, global = true, default_value_t = 1000)]
+    #[arg(value_parser = clap::value_parser!(u64).range(MIN_DEADLOCK_INTERVAL_MS..))]
+    deadlock_interval: u64,
+    /// Milliseconds each control-plane call may take; slow heartbeats count as missed polls
+    #[arg(long, global = true, default_value_t = client::DEFAULT_RPC_TIMEOUT_MS)]
+    #[arg(value_parser = clap::value_parser!(u64).range(MIN_RPC_TIMEOUT_MS..))]
+    rpc_timeout: u64,
+    /// Ring the terminal bell and flash the header on deadlocks, leader changes and disconnects
+    #[arg(long, global = true)]
+    alert_bell: bool,
+    /// POST a JSON payload to this URL on the same events as --alert-bell
+    #[arg(long, global = true)]
+    alert_webhook: Option<String>,
+    /// Append every fetched sample to this JSONL file for later `replay`
+    #[arg(long, global = true)]
+    record: Option<PathBuf>,
+    /// Size at which the recording is rotated to `<file>.1`
+    #[arg(long, global = true, default_value_t = recording::DEFAULT_MAX_BYTES)]
+    record_max_bytes: u64,
+    /// Key binding preset; `vim` adds h/j/k/l, gg and G
+    #[arg(long, global = true, value_enum, default_value_t = KeymapStyle::Default)]
+    keymap: KeymapStyle,
+    /// Color preset; light backgrounds want `light`
+    #[arg(long, global = true, value_enum, env = "PRB_THEME", default_value_t = ThemeName::Dark)]
+    theme: ThemeName,
+    /// Disable all colors (also enabled by a non-empty NO_COLOR)
+    #[arg(long, global = true)]
+    no_color: bool,
+    /// Seconds of deadlock history the Timeline tab's axis covers
+    #[arg(long, global = true, default_value_t = timeline::DEFAULT_HORIZON_SECS)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    timeline_horizon: u64,
 }
 
 #[derive(Subcommand, Clone)]
-enum Mode {
-    /// Online mode connecting to a running node via gRPC
+enum Command {
+    /// Online mode connecting to one or more running nodes via gRPC
     Online {
-        #[arg(short, long, default_value = "http://127.0.0.1:50051")]
-        address: String,
+        /// Node to watch; repeat the flag or pass a comma-separated list to watch a cluster
+        #[arg(short, long = "address", value_delimiter = ',')]
+        addresses: Vec<String>,
     },
-    /// Offline mode inspecting a local database
+    /// Offline mode inspecting a saved snapshot (file or directory)
     Offline {
         #[arg(short, long)]
         path: PathBuf,
     },
+    /// Replay a session captured with --record (space: play/pause, +/-: speed)
+    Replay { path: PathBuf },
+    /// Print node status and exit (non-zero when deadlocks exist or the node is unreachable)
+    Status(OneShotArgs),
+    /// Print detected deadlocks and exit
+    Deadlocks(OneShotArgs),
+    /// Print recent Raft log lines and exit
+    Logs {
+        #[command(flatten)]
+        target: OneShotArgs,
+        #[arg(short, long, default_value_t = 20)]
+        limit: u32,
+    },
+    /// Print the deadlocks and recent log lines involving one resource
+    Resource {
+        resource_id: String,
+        #[command(flatten)]
+        target: OneShotArgs,
+    },
+}
+
+#[derive(Args, Clone)]
+struct OneShotArgs {
+    #[arg(short, long, default_value = DEFAULT_ADDRESS)]
+    address: String,
+    /// Emit machine-readable JSON instead of a table
+    #[arg(long)]
+    json: bool,
+}
+
+/// Data source of the interactive dashboard.
+#[derive(Clone)]
+enum Mode {
+    Online { addresses: Vec<String> },
+    Offline { path: PathBuf },
+    Replay { path: PathBuf },
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() -> Result<ExitCode, Box<dyn Error>> {
     let cli = Cli::parse();
+    let color = !cli.no_color && !theme::no_color_requested() && io::stdout().is_terminal();
+    let rpc_timeout = Duration::from_millis(cli.rpc_timeout);
 
-    // Setup terminal
+    let mode = match cli.command {
+        Command::Online { mut addresses } => {
+            if addresses.is_empty() {
+                addresses.push(DEFAULT_ADDRESS.to_string());
+            }
+            Mode::Online { addresses }
+        }
+        Command::Offline { path } => Mode::Offline { path },
+        Command::Replay { path } => Mode::Replay { path },
+        Command::Status(args) => {
+            return Ok(commands::run(args, Query::Status, color, rpc_timeout).await);
+        }
+        Command::Deadlocks(args) => {
+            return Ok(commands::run(args, Query::Deadlocks, color, rpc_timeout).await);
+        }
+        Command::Logs { target, limit } => {
+            return Ok(commands::run(target, Query::Logs { limit }, color, rpc_timeout).await);
+        }
+        Command::Resource {
+            resource_id,
+            target,
+        } => {
+            return Ok(
+                commands::run(target, Query::Resource { resource_id }, color, rpc_timeout).await,
+            );
+        }
+    };
+
+    // Fail before taking over the terminal if the recording can't be opened
+    let recorder = match (&mode, &cli.record) {
+        (Mode::Online { .. }, Some(path)) => Some(Recorder::create(path, cli.record_max_bytes)?),
+        _ => None,
+    };
+
+    // Setup terminal; a panic must not leave the user's shell in raw mode
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        let _ = execute!(io::stdout(), Show);
+        default_hook(info);
+    }));
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let app = App::new(cli.mode);
-    let res = run_app(&mut terminal, app).await;
+    let mut app = App::new(mode);
+    app.log_export = cli.log_export;
+    app.keymap = keymap::Keymap::new(cli.keymap);
+    app.theme = Theme::new(cli.theme);
+    if cli.no_color || theme::no_color_requested() {
+        app.theme = app.theme.without_color();
+    }
+    app.timeline_horizon = Duration::from_secs(cli.timeline_horizon);
+    app.alert_bell = cli.alert_bell;
+    app.alert_webhook = cli.alert_webhook;
+    let res = match app.mode.clone() {
+        Mode::Online { .. } => {
+            let source = LiveSource::new(
+                &app.endpoints,
+                Duration::from_millis(cli.poll_interval),
+                Duration::from_millis(cli.deadlock_interval),
+                rpc_timeout,
+            )
+            .with_recorder(recorder)
+            .spawn();
+            run_app(&mut terminal, app, source).await
+        }
+        Mode::Offline { path } => {
+            match Snapshot::load(&path) {
+                Ok(snapshot) => app.load_snapshot(snapshot),
+                Err(e) => app.banner = Some(e.to_string()),
+            }
+            run_app(&mut terminal, app, Idle).await
+        }
+        Mode::Replay { path } => match recording::load(&path) {
+            Ok(records) => run_app(&mut terminal, app, ReplaySource::new(records)).await,
+            Err(e) => {
+                app.banner = Some(e.to_string());
+                run_app(&mut terminal, app, Idle).await
+            }
+        },
+    };
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    restore_terminal()?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
         println!("{:?}", err);
     }
 
-    Ok(())
-}
-
-use std::time::Instant;
-
-#[derive(Debug, Clone, PartialEq)]
-enum ConnectionStatus {
-    OfflineMode,
-    Connected,
-    Reconnecting(usize), // retries
-    Disconnected(Instant),
+    Ok(ExitCode::SUCCESS)
 }
 
-struct App {
-    mode: Mode,
-    tab_index: usize,
-    should_quit: bool,
-    logs: VecDeque<String>,
-    deadlocks: Vec<String>,
-    paused: bool,
-    filter_input: String,
-    is_typing: bool,
-    tick_count: u64,
-    connection_status: ConnectionStatus,
-    endpoint: Option<String>,
-}
-
-impl App {
-    fn new(mode: Mode) -> Self {
-        let (connection_status, endpoint) = match &mode {
-            Mode::Online { address } => {
-                let addr = if address.starts_with("http://") || address.starts_with("https://") {
-                    address.clone()
-                } else {
-                    format!("http://{}", address)
-                };
-                (ConnectionStatus::Disconnected(Instant::now()), Some(addr))
-            }
-            Mode::Offline { .. } => (ConnectionStatus::OfflineMode, None),
-        };
-
-        Self {
-            mode,
-            tab_index: 0,
-            should_quit: false,
-            logs: VecDeque::from(vec![
-                "System initialized".to_string(),
-                "Ready to inspect".to_string(),
-            ]),
-            deadlocks: Vec::new(),
-            paused: false,
-            filter_input: String::new(),
-            is_typing: false,
-            tick_count: 0,
-            connection_status,
-            endpoint,
-        }
-    }
-}
-
-async fn run_app<B: ratatui::backend::Backend<Error = io::Error>>(
+async fn run_app<B: ratatui::backend::Backend<Error = io::Error>, S: DataSource>(
     terminal: &mut Terminal<B>,
     mut app: App,
+    mut source: S,
 ) -> io::Result<()> {
-    use praborrow_lease::grpc::proto::control_plane_client::ControlPlaneClient;
-    use praborrow_lease::grpc::proto::{Empty, LogRequest};
+    let mut last_draw: Option<Instant> = None;
+    let mut dirty = true;
 
-    // Client handle. If None, we are disconnected/reconnecting.
-    let mut client: Option<ControlPlaneClient<tonic::transport::Channel>> = None;
-    let mut last_reconnect_attempt = Instant::now();
-    let mut reconnect_backoff_secs = 1;
+    let mut alert_engine = AlertEngine::new(ALERT_DEBOUNCE);
+    let webhook = app.alert_webhook.clone().map(WebhookSender::new);
+    let (webhook_failures, mut webhook_failure_rx) = mpsc::unbounded_channel();
 
     loop {
-        terminal.draw(|f| ui(f, &app))?;
+        // Redraw only on change or at a slow cadence; full repaints flicker over SSH
+        if dirty || last_draw.is_none_or(|at| at.elapsed() >= REDRAW_INTERVAL) {
+            terminal.draw(|f| ui(f, &app))?;
+            last_draw = Some(Instant::now());
+            dirty = false;
+        }
 
         #[allow(clippy::collapsible_if)]
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            dirty = true;
+            if let Event::Resize(..) = event {
+                // tmux pane resizes can leave stale cells behind; repaint everything
+                terminal.clear()?;
+            }
+            if let Event::Key(key) = event {
                 // ... handle inputs ...
                 if app.is_typing {
                     match key.code {
                         KeyCode::Enter => app.is_typing = false,
                         KeyCode::Esc => {
                             app.is_typing = false;
-                            app.filter_input.clear();
+                            app.clear_filter();
                         }
                         KeyCode::Backspace => {
-                            app.filter_input.pop();
+                            app.filter_pop();
                         }
                         KeyCode::Char(c) => {
-                            app.filter_input.push(c);
+                            app.filter_push(c);
                         }
                         _ => {}
                     }
-                } else {
-                    match key.code {
-                        KeyCode::Char('q') => {
-                            app.should_quit = true;
-                        }
-                        KeyCode::Tab => {
-                            app.tab_index = (app.tab_index + 1) % 3;
-                        }
-                        KeyCode::Char('p') => {
-                            app.paused = !app.paused;
+                } else if app.show_help {
+                    if matches!(key.code, KeyCode::Esc | KeyCode::Char('?')) {
+                        app.show_help = false;
+                    }
+                } else if app.inspection.is_some() {
+                    if matches!(
+                        key.code,
+                        KeyCode::Esc | KeyCode::Char('i') | KeyCode::Char('q')
+                    ) {
+                        app.inspection = None;
+                    }
+                } else if let Some(action) = app.keymap.resolve(key.code, app.tab_index) {
+                    match action {
+                        KeyAction::Quit => app.should_quit = true,
+                        KeyAction::Help => app.show_help = true,
+                        KeyAction::NextTab => app.next_tab(),
+                        KeyAction::PrevTab => app.prev_tab(),
+                        KeyAction::Up => app.move_selection(-1),
+                        KeyAction::Down => app.move_selection(1),
+                        KeyAction::Top => app.move_selection(isize::MIN),
+                        KeyAction::Bottom => app.move_selection(isize::MAX),
+                        KeyAction::PageUp => {
+                            let len = app.visible_logs().len();
+                            app.log_view.page_up(log_page_size(terminal)?, len);
                         }
-                        KeyCode::Char('/') => {
-                            app.is_typing = true;
+                        KeyAction::PageDown => {
+                            let len = app.visible_logs().len();
+                            app.log_view.page_down(log_page_size(terminal)?, len);
                         }
-                        KeyCode::Esc => {
-                            app.filter_input.clear();
+                        KeyAction::ToggleFollow => app.log_view.toggle_follow(),
+                        KeyAction::CopyLine => {
+                            if let Some(line) = app.selected_log() {
+                                app.notice = Some(
+                                    logview::export_line(&line, app.log_export.as_deref())
+                                        .unwrap_or_else(|e| format!("Copy failed: {}", e)),
+                                );
+                            }
                         }
-                        _ => {}
-                    }
-                }
-            }
-        }
-
-        // Connection Management Logic
-        if let Some(endpoint) = &app.endpoint
-            && client.is_none()
-        {
-            // Try to connect if cooldown passed
-            if last_reconnect_attempt.elapsed() >= Duration::from_secs(reconnect_backoff_secs) {
-                last_reconnect_attempt = Instant::now();
-
-                app.connection_status =
-                    ConnectionStatus::Reconnecting(reconnect_backoff_secs as usize);
-                // Use connect_lazy? connect() is async but can timeout.
-                // Let's try direct connect with timeout validation.
-                match ControlPlaneClient::connect(endpoint.clone()).await {
-                    Ok(c) => {
-                        client = Some(c);
-                        app.connection_status = ConnectionStatus::Connected;
-                        reconnect_backoff_secs = 1; // reset
-                        app.logs.push_front("Connected to backend.".to_string());
-                    }
-                    Err(e) => {
-                        app.logs.push_front(format!("Connection failed: {}", e));
-                        reconnect_backoff_secs = (reconnect_backoff_secs * 2).min(30); // max 30s
-                        app.connection_status = ConnectionStatus::Disconnected(Instant::now());
-                    }
-                }
-            }
-        }
-
-        if !app.paused {
-            app.tick_count += 1;
-
-            let mut should_reconnect = false;
-
-            if let Some(c) = &mut client {
-                // Poll gRPC every ~1s (10 ticks)
-                #[allow(clippy::collapsible_if, clippy::manual_is_multiple_of)]
-                if app.tick_count % HEARTBEAT_TICK_RATE == 0 {
-                    // Fetch Status
-                    match c.get_node_status(tonic::Request::new(Empty {})).await {
-                        Ok(response) => {
-                            let status = response.into_inner();
-                            app.logs.push_front(format!(
-                                "STATUS: {} (Term {})",
-                                status.state, status.current_term
-                            ));
-                            app.connection_status = ConnectionStatus::Connected;
+                        KeyAction::Pause => app.toggle_pause(),
+                        KeyAction::Inspect => {
+                            if let Some(resource_id) = app.selected_resource() {
+                                app.inspection = Some(app.inspect(&resource_id));
+                            }
                         }
-                        Err(e) => {
-                            // If status check fails, assume disconnection
-                            app.logs.push_front(format!("Heartbeat failed: {}", e));
-                            should_reconnect = true;
+                        KeyAction::StartFilter => app.is_typing = true,
+                        KeyAction::ClearFilter => app.clear_filter(),
+                        KeyAction::ScrubBack => app.scrub_back(),
+                        KeyAction::ScrubForward => app.scrub_forward(),
+                        KeyAction::TogglePlay => {
+                            if let Some(playback) = source.playback() {
+                                playback.toggle_play();
+                            }
                         }
-                    }
-
-                    // Fetch Logs (if still connected)
-                    if !should_reconnect {
-                        if let Ok(response) = c
-                            .get_recent_logs(tonic::Request::new(LogRequest { limit: 5 }))
-                            .await
-                        {
-                            let server_logs = response.into_inner().logs;
-                            for log in server_logs {
-                                if !app.logs.contains(&log) {
-                                    app.logs.push_front(log);
-                                }
+                        KeyAction::Faster => {
+                            if let Some(playback) = source.playback() {
+                                playback.faster();
                             }
                         }
-                    }
-
-                    // Fetch Deadlocks
-                    if !should_reconnect {
-                        if let Ok(response) = c.get_deadlocks(tonic::Request::new(Empty {})).await {
-                            app.deadlocks = response.into_inner().deadlocks;
+                        KeyAction::Slower => {
+                            if let Some(playback) = source.playback() {
+                                playback.slower();
+                            }
                         }
                     }
-
-                    // Cap logs
-                    if app.logs.len() > 1000 {
-                        app.logs.truncate(1000);
-                    }
                 }
             }
+        }
 
-            if should_reconnect {
-                client = None;
-                app.connection_status = ConnectionStatus::Disconnected(Instant::now());
+        // Live collection runs on its own task, even while paused; App buffers what the
+        // frozen view hides
+        let samples = source.poll().await;
+        dirty |= !samples.is_empty();
+        let live = source.is_live();
+        for sample in samples {
+            let mut alerts = Vec::new();
+            if live && let Sample::Deadlocks { node, cycles } = &sample {
+                alerts.extend(alert_engine.observe_deadlocks(node, cycles, Instant::now()));
             }
-
-            // Every 50 ticks (~5s), simulate a deadlock check
-            #[allow(clippy::manual_is_multiple_of)]
-            if app.tick_count % DEADLOCK_CHECK_TICK_RATE == 0 {
-                // Keep simulation for offline mode
-                if matches!(app.mode, Mode::Offline { .. }) {
-                    app.deadlocks.clear();
-                }
+            let is_cluster = matches!(sample, Sample::Cluster { .. });
+            app.apply(sample);
+            if live && is_cluster {
+                alerts.extend(alert_engine.observe_cluster(&app.cluster, Instant::now()));
+            }
+            for alert in alerts {
+                raise_alert(&mut app, webhook.as_ref(), &webhook_failures, alert);
             }
         }
+        app.timeline = source.timeline();
 
-        if app.should_quit {
-            return Ok(());
+        // Webhook failures are only ever logged; they must not interrupt monitoring
+        while let Ok(failure) = webhook_failure_rx.try_recv() {
+            app.record_log(failure);
+            dirty = true;
         }
-    }
-}
 
-fn ui(frame: &mut ratatui::Frame, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Main content
-            Constraint::Length(3), // Footer
-        ])
-        .split(frame.area());
-
-    // Header
-    let (status_text, status_color) = match &app.connection_status {
-        ConnectionStatus::Connected => ("CONNECTED", Color::Green),
-        ConnectionStatus::Reconnecting(_retry) => {
-            // Can't return string with lifetime issues here easily, let's format later or use fixed strings
-            // Actually let's use a Cow or just format the whole header line
-            ("RECONNECTING", Color::Yellow)
+        if app.should_quit {
+            return Ok(());
         }
-        ConnectionStatus::Disconnected(_) => ("DISCONNECTED", Color::Red),
-        ConnectionStatus::OfflineMode => ("OFFLINE", Color::Gray),
-    };
-
-    let mode_str = match &app.mode {
-        Mode::Online { address } => format!("Online: {}", address),
-        Mode::Offline { path } => format!("Offline: {:?}", path),
-    };
-
-    let time_status = if app.paused { "PAUSED" } else { "RUNNING" };
-
-    let header_text = format!(
-        "PraBorrow Dashboard - {} | Status: {} | {}",
-        mode_str, status_text, time_status
-    );
-
-    // For Reconnecting, maybe append retry count
-    let header_text = if let ConnectionStatus::Reconnecting(secs) = app.connection_status {
-        format!("{} (Backoff {}s)", header_text, secs)
-    } else {
-        header_text
-    };
-
-    let header = Paragraph::new(header_text)
-        .style(
-            Style::default()
-                .fg(status_color)
-                .add_modifier(Modifier::BOLD),
-        )
-        .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(header, chunks[0]);
-
-    // Main Content (Tabs)
-    let titles: Vec<Line> = ["Overview", "Log Explorer", "Deadlocks"]
-        .iter()
-        .cloned()
-        .map(Line::from)
-        .collect();
-
-    let tabs = Tabs::new(titles)
-        .select(app.tab_index)
-        .block(Block::default().title("View").borders(Borders::ALL))
-        .style(Style::default().fg(Color::White))
-        .highlight_style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        );
-
-    // Split main area into tabs and content
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0)])
-        .split(chunks[1]);
-
-    frame.render_widget(tabs, main_chunks[0]);
-
-    match app.tab_index {
-        0 => render_overview(frame, main_chunks[1], app),
-        1 => render_log_explorer(frame, main_chunks[1], app),
-        2 => render_deadlocks(frame, main_chunks[1], app),
-        _ => {}
     }
-
-    // Footer
-    // Footer
-    let footer_text = if app.is_typing {
-        format!("Filter: {}_", app.filter_input)
-    } else if !app.filter_input.is_empty() {
-        format!(
-            "Filter: {} (Press '/' to edit, 'Esc' to clear) | 'p' Pause | 'q' Quit",
-            app.filter_input
-        )
-    } else {
-        "Press '/' to filter, 'p' to pause, 'q' to quit, 'Tab' to switch views".to_string()
-    };
-
-    let footer = Paragraph::new(footer_text)
-        .style(Style::default().fg(Color::Gray))
-        .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(footer, chunks[2]);
 }
 
-fn render_overview(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, _app: &App) {
-    let text = vec![
-        Line::from(Span::raw("System Status: Operational")),
-        Line::from(Span::raw("Nodes: 3/3 Online")),
-        Line::from(Span::raw("Consensus: Stable")),
-    ];
-    let paragraph = Paragraph::new(text).block(
-        Block::default()
-            .title("Cluster Overview")
-            .borders(Borders::ALL),
-    );
-    frame.render_widget(paragraph, area);
+/// Leaves raw mode and the alternate screen; also run from the panic hook.
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)
 }
 
-fn render_log_explorer(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
-    let items: Vec<ListItem> = app
-        .logs
-        .iter()
-        .filter(|log| {
-            if app.filter_input.is_empty() {
-                true
-            } else {
-                log.to_lowercase()
-                    .contains(&app.filter_input.to_lowercase())
+/// Logs an alert, then rings the bell and fires the webhook when enabled.
+fn raise_alert(
+    app: &mut App,
+    webhook: Option<&WebhookSender>,
+    failures: &mpsc::UnboundedSender<String>,
+    alert: Alert,
+) {
+    app.record_log(alert.describe());
+    if app.alert_bell {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+        app.alert_flash = Some((alert.describe(), Instant::now()));
+    }
+    if let Some(webhook) = webhook.cloned() {
+        let failures = failures.clone();
+        tokio::spawn(async move {
+            if let Err(e) = webhook.send(&alert).await {
+                let _ = failures.send(format!("Alert webhook failed: {}", e));
             }
-        })
-        .map(|log| ListItem::new(Line::from(Span::raw(log))))
-        .collect();
-
-    let list = List::new(items).block(Block::default().title("Raft Logs").borders(Borders::ALL));
-    frame.render_widget(list, area);
+        });
+    }
 }
 
-fn render_deadlocks(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
-    if app.deadlocks.is_empty() {
-        let paragraph = Paragraph::new(
-            "\n  No deadlocks detected in Sovereign resource graph.\n  System is healthy.",
-        )
-        .style(Style::default().fg(Color::Green))
-        .block(
-            Block::default()
-                .title("Deadlock Detector")
-                .borders(Borders::ALL),
-        );
-        frame.render_widget(paragraph, area);
-    } else {
-        let items: Vec<ListItem> = app
-            .deadlocks
-            .iter()
-            .map(|d| {
-                ListItem::new(Line::from(vec![
-                    Span::styled(
-                        " ⚠ ",
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(d),
-                ]))
-            })
-            .collect();
-
-        let list = List::new(items)
-            .block(
-                Block::default()
-                    .title("DEADLOCKS DETECTED")
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Red)),
-            )
-            .style(Style::default().fg(Color::LightRed));
-        frame.render_widget(list, area);
-    }
+/// Rows scrolled by PageUp/PageDown: the log list height minus the surrounding chrome.
+fn log_page_size<B: ratatui::backend::Backend<Error = io::Error>>(
+    terminal: &Terminal<B>,
+) -> io::Result<usize> {
+    Ok(terminal.size()?.height.saturating_sub(11).max(1) as usize)
 }
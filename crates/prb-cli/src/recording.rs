@@ -0,0 +1,395 @@
+//! `--record` session files and the virtual clock that replays them.
+//!
+//! A recording is JSONL: one [`Record`] per line, each a [`Sample`] stamped with the
+//! wall-clock time it was collected.
+
+use crate::source::Sample;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Default `--record-max-bytes`: 64 MiB before the file is rotated.
+pub const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Playback speeds cycled by `+` and `-`.
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const NORMAL_SPEED: usize = 2;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Record {
+    /// Unix time in milliseconds.
+    pub at_ms: u64,
+    #[serde(flatten)]
+    pub sample: Sample,
+}
+
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    at_ms: u64,
+    #[serde(flatten)]
+    sample: &'a Sample,
+}
+
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    #[error("failed to read recording {path:?}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("corrupt recording at line {line}: {source}")]
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("recording {0:?} contains no samples")]
+    Empty(PathBuf),
+}
+
+/// Appends samples to the `--record` file, rotating it to `<file>.1` when it would exceed
+/// `max_bytes`.
+pub struct Recorder {
+    path: PathBuf,
+    max_bytes: u64,
+    file: BufWriter<File>,
+    written: u64,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            max_bytes,
+            file: BufWriter::new(file),
+            written,
+        })
+    }
+
+    pub fn append(&mut self, samples: &[Sample]) -> io::Result<()> {
+        let at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        for sample in samples {
+            let line = serde_json::to_string(&RecordRef { at_ms, sample })?;
+            let len = line.len() as u64 + 1;
+            if self.written > 0 && self.written + len > self.max_bytes {
+                self.rotate()?;
+            }
+            writeln!(self.file, "{}", line)?;
+            self.written += len;
+        }
+        self.file.flush()
+    }
+
+    /// Keeps a single previous generation; older data is dropped.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&self.path, rotated)?;
+        self.file = BufWriter::new(File::create(&self.path)?);
+        self.written = 0;
+        Ok(())
+    }
+}
+
+/// Reads a recording, ordered by collection time.
+pub fn load(path: &Path) -> Result<Vec<Record>, RecordingError> {
+    let io_error = |source| RecordingError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let reader = BufReader::new(File::open(path).map_err(io_error)?);
+    let mut records = Vec::new();
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line.map_err(io_error)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line).map_err(|source| RecordingError::Parse {
+            line: idx + 1,
+            source,
+        })?;
+        records.push(record);
+    }
+    if records.is_empty() {
+        return Err(RecordingError::Empty(path.to_path_buf()));
+    }
+    records.sort_by_key(|record: &Record| record.at_ms);
+    Ok(records)
+}
+
+/// Virtual clock over a recording: real time scaled by the playback speed.
+pub struct Playback {
+    records: Vec<Record>,
+    /// Index of the first record not yet emitted.
+    next: usize,
+    /// Milliseconds since the first record.
+    clock_ms: f64,
+    speed: usize,
+    playing: bool,
+}
+
+impl Playback {
+    /// `records` must be non-empty and sorted, as returned by [`load`].
+    pub fn new(records: Vec<Record>) -> Self {
+        Self {
+            records,
+            next: 0,
+            clock_ms: 0.0,
+            speed: NORMAL_SPEED,
+            playing: true,
+        }
+    }
+
+    fn start_ms(&self) -> u64 {
+        self.records.first().map_or(0, |r| r.at_ms)
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        self.records.last().map_or(0, |r| r.at_ms) - self.start_ms()
+    }
+
+    /// Moves the clock forward by `elapsed` real time and returns the samples now due.
+    pub fn advance(&mut self, elapsed: Duration) -> Vec<Sample> {
+        if self.playing {
+            self.clock_ms = (self.clock_ms + elapsed.as_secs_f64() * 1000.0 * SPEEDS[self.speed])
+                .min(self.duration_ms() as f64);
+        }
+        let start = self.start_ms();
+        let mut due = Vec::new();
+        while let Some(record) = self.records.get(self.next)
+            && (record.at_ms - start) as f64 <= self.clock_ms
+        {
+            due.push(record.sample.clone());
+            self.next += 1;
+        }
+        if self.next == self.records.len() {
+            self.playing = false;
+        }
+        due
+    }
+
+    pub fn toggle_play(&mut self) {
+        self.playing = !self.playing;
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    /// Header segment, e.g. `Replay 00:12/01:00 x2 PLAYING`.
+    pub fn position(&self) -> String {
+        let state = if self.playing {
+            "PLAYING"
+        } else if self.next == self.records.len() {
+            "ENDED"
+        } else {
+            "PAUSED"
+        };
+        format!(
+            "Replay {}/{} x{} {}",
+            format_clock(self.clock_ms as u64),
+            format_clock(self.duration_ms()),
+            SPEEDS[self.speed],
+            state
+        )
+    }
+}
+
+fn format_clock(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// Serde adapter for [`ConnectionStatus`](crate::app::ConnectionStatus), whose
+/// `Instant` payloads cannot be persisted; they are restored relative to load time.
+pub mod connection_status {
+    use crate::app::ConnectionStatus;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Instant;
+
+    pub fn serialize<S: Serializer>(status: &ConnectionStatus, s: S) -> Result<S::Ok, S::Error> {
+        match status {
+            ConnectionStatus::OfflineMode => s.serialize_str("offline"),
+            ConnectionStatus::Connected => s.serialize_str("connected"),
+            ConnectionStatus::Reconnecting(secs) => {
+                s.serialize_str(&format!("reconnecting:{}", secs))
+            }
+            ConnectionStatus::Disconnected(_) => s.serialize_str("disconnected"),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<ConnectionStatus, D::Error> {
+        let raw = String::deserialize(d)?;
+        Ok(match raw.as_str() {
+            "offline" => ConnectionStatus::OfflineMode,
+            "connected" => ConnectionStatus::Connected,
+            other => match other.strip_prefix("reconnecting:") {
+                Some(secs) => ConnectionStatus::Reconnecting(secs.parse().unwrap_or(0)),
+                None => ConnectionStatus::Disconnected(Instant::now()),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ConnectionStatus;
+    use crate::cluster::NodeSample;
+
+    fn event(line: &str) -> Sample {
+        Sample::Event {
+            line: line.to_string(),
+        }
+    }
+
+    fn lines(samples: &[Sample]) -> Vec<String> {
+        samples
+            .iter()
+            .map(|sample| match sample {
+                Sample::Event { line } => line.clone(),
+                other => panic!("unexpected sample {:?}", other),
+            })
+            .collect()
+    }
+
+    fn record(at_ms: u64, line: &str) -> Record {
+        Record {
+            at_ms,
+            sample: event(line),
+        }
+    }
+
+    #[test]
+    fn test_recorder_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let mut recorder = Recorder::create(&path, DEFAULT_MAX_BYTES).unwrap();
+        recorder.append(&[event("one")]).unwrap();
+        recorder
+            .append(&[Sample::Cluster {
+                nodes: vec![NodeSample {
+                    endpoint: "http://n1".into(),
+                    connection: ConnectionStatus::Reconnecting(4),
+                    status: None,
+                    missed_polls: 1,
+                    last_seen_ms: Some(1_700_000_000_000),
+                }],
+            }])
+            .unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(raw.lines().next().unwrap().contains(r#""kind":"event""#));
+        assert!(raw.contains(r#""connection":"reconnecting:4""#));
+
+        let records = load(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        let Sample::Cluster { nodes } = &records[1].sample else {
+            panic!("expected a cluster sample");
+        };
+        assert_eq!(nodes[0].connection, ConnectionStatus::Reconnecting(4));
+        assert_eq!(nodes[0].missed_polls, 1);
+    }
+
+    #[test]
+    fn test_recorder_rotates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let mut recorder = Recorder::create(&path, 60).unwrap();
+        recorder.append(&[event("first")]).unwrap();
+        recorder.append(&[event("second")]).unwrap();
+
+        let rotated = dir.path().join("session.jsonl.1");
+        assert_eq!(
+            lines(&[load(&rotated).unwrap()[0].sample.clone()]),
+            ["first"]
+        );
+        assert_eq!(lines(&[load(&path).unwrap()[0].sample.clone()]), ["second"]);
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+
+        fs::write(&path, "\n\n").unwrap();
+        assert!(matches!(load(&path), Err(RecordingError::Empty(_))));
+
+        fs::write(
+            &path,
+            "{\"at_ms\":1,\"kind\":\"event\",\"line\":\"ok\"}\nnot json\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            load(&path),
+            Err(RecordingError::Parse { line: 2, .. })
+        ));
+
+        let missing = dir.path().join("missing.jsonl");
+        assert!(matches!(load(&missing), Err(RecordingError::Io { .. })));
+    }
+
+    #[test]
+    fn test_load_sorts_by_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        fs::write(
+            &path,
+            "{\"at_ms\":20,\"kind\":\"event\",\"line\":\"late\"}\n{\"at_ms\":10,\"kind\":\"event\",\"line\":\"early\"}\n",
+        )
+        .unwrap();
+        let samples: Vec<Sample> = load(&path).unwrap().into_iter().map(|r| r.sample).collect();
+        assert_eq!(lines(&samples), ["early", "late"]);
+    }
+
+    #[test]
+    fn test_playback_virtual_clock() {
+        let mut playback = Playback::new(vec![
+            record(10_000, "a"),
+            record(11_000, "b"),
+            record(13_000, "c"),
+            record(70_000, "d"),
+        ]);
+        assert_eq!(playback.duration_ms(), 60_000);
+        assert_eq!(lines(&playback.advance(Duration::ZERO)), ["a"]);
+        assert_eq!(playback.position(), "Replay 00:00/01:00 x1 PLAYING");
+
+        playback.faster();
+        assert_eq!(lines(&playback.advance(Duration::from_millis(500))), ["b"]);
+
+        playback.toggle_play();
+        assert!(playback.advance(Duration::from_secs(10)).is_empty());
+        assert_eq!(playback.position(), "Replay 00:01/01:00 x2 PAUSED");
+
+        playback.toggle_play();
+        playback.slower();
+        playback.slower();
+        assert_eq!(lines(&playback.advance(Duration::from_secs(4))), ["c"]);
+        assert_eq!(playback.position(), "Replay 00:03/01:00 x0.5 PLAYING");
+
+        // The clock stops at the last record
+        assert_eq!(lines(&playback.advance(Duration::from_secs(600))), ["d"]);
+        assert_eq!(playback.position(), "Replay 01:00/01:00 x0.5 ENDED");
+    }
+
+    #[test]
+    fn test_playback_speed_is_bounded() {
+        let mut playback = Playback::new(vec![record(0, "a")]);
+        for _ in 0..10 {
+            playback.faster();
+        }
+        assert!(playback.position().contains("x16"));
+        for _ in 0..10 {
+            playback.slower();
+        }
+        assert!(playback.position().contains("x0.25"));
+    }
+}
@@ -0,0 +1,205 @@
+//! On-disk snapshot format consumed by offline mode.
+//!
+//! A snapshot is the serde JSON encoding of [`Snapshot`], either passed directly or
+//! stored as `snapshot.json` inside a directory. The lease node does not export
+//! snapshots itself, so whatever writes one serializes a `Snapshot` directly.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name looked up when the offline path is a directory.
+pub const SNAPSHOT_FILE_NAME: &str = "snapshot.json";
+
+/// Highest snapshot format version this build understands.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    #[serde(default)]
+    pub node_id: Option<u64>,
+    pub taken_at_ms: u64,
+    #[serde(default)]
+    pub log: Vec<LogEntry>,
+    #[serde(default)]
+    pub leases: Vec<LeaseEntry>,
+    #[serde(default)]
+    pub deadlocks: Vec<DeadlockIncident>,
+}
+
+/// A single replicated Raft log entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub index: u64,
+    pub term: u64,
+    pub command: String,
+}
+
+/// A row of the lease table at the time the snapshot was taken.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaseEntry {
+    pub resource_id: String,
+    pub holder: String,
+    pub lease_id: String,
+    pub epoch: u64,
+    pub expires_at_ms: u64,
+}
+
+/// A deadlock detected by the node, anchored to the log index at detection time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadlockIncident {
+    pub detected_at_ms: u64,
+    pub log_index: u64,
+    pub cycle: Vec<String>,
+}
+
+impl DeadlockIncident {
+    /// Renders the cycle as `a -> b -> a`.
+    pub fn describe(&self) -> String {
+        let mut path = self.cycle.join(" -> ");
+        if let Some(first) = self.cycle.first() {
+            path.push_str(" -> ");
+            path.push_str(first);
+        }
+        path
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("failed to read snapshot {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("corrupt snapshot: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("unsupported snapshot version {0} (max {SNAPSHOT_VERSION})")]
+    UnsupportedVersion(u32),
+    #[error("log index {index} appears after {previous}; entries must be strictly increasing")]
+    NonMonotonicLog { previous: u64, index: u64 },
+}
+
+impl Snapshot {
+    /// Loads a snapshot from a file, or from `snapshot.json` inside a directory.
+    pub fn load(path: &Path) -> Result<Self, SnapshotError> {
+        let file = if path.is_dir() {
+            path.join(SNAPSHOT_FILE_NAME)
+        } else {
+            path.to_path_buf()
+        };
+        let raw = std::fs::read_to_string(&file).map_err(|source| SnapshotError::Io {
+            path: file.clone(),
+            source,
+        })?;
+        Self::parse(&raw)
+    }
+
+    /// Parses and validates a snapshot document.
+    pub fn parse(raw: &str) -> Result<Self, SnapshotError> {
+        let snapshot: Snapshot = serde_json::from_str(raw)?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        for pair in snapshot.log.windows(2) {
+            if pair[1].index <= pair[0].index {
+                return Err(SnapshotError::NonMonotonicLog {
+                    previous: pair[0].index,
+                    index: pair[1].index,
+                });
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Index of the newest log entry, if any.
+    pub fn last_index(&self) -> Option<u64> {
+        self.log.last().map(|entry| entry.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = r#"{
+        "version": 1,
+        "node_id": 2,
+        "taken_at_ms": 10000,
+        "log": [
+            {"index": 1, "term": 1, "command": "grant db"},
+            {"index": 2, "term": 1, "command": "grant cache"},
+            {"index": 4, "term": 2, "command": "revoke db"}
+        ],
+        "leases": [
+            {"resource_id": "cache", "holder": "node-2", "lease_id": "l-7", "epoch": 3, "expires_at_ms": 12500}
+        ],
+        "deadlocks": [
+            {"detected_at_ms": 9000, "log_index": 2, "cycle": ["db", "cache"]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_valid() {
+        let snapshot = Snapshot::parse(SNAPSHOT).unwrap();
+        assert_eq!(snapshot.node_id, Some(2));
+        assert_eq!(snapshot.log.len(), 3);
+        assert_eq!(snapshot.last_index(), Some(4));
+        assert_eq!(snapshot.deadlocks[0].describe(), "db -> cache -> db");
+    }
+
+    #[test]
+    fn test_serialized_snapshot_parses_back() {
+        let snapshot = Snapshot::parse(SNAPSHOT).unwrap();
+        let raw = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(Snapshot::parse(&raw).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn test_optional_sections_default_to_empty() {
+        let snapshot = Snapshot::parse(r#"{"version": 1, "taken_at_ms": 0}"#).unwrap();
+        assert_eq!(snapshot.node_id, None);
+        assert!(snapshot.log.is_empty() && snapshot.leases.is_empty());
+        assert_eq!(snapshot.last_index(), None);
+    }
+
+    #[test]
+    fn test_rejects_newer_version() {
+        let err = Snapshot::parse(r#"{"version": 2, "taken_at_ms": 0}"#).unwrap_err();
+        assert!(matches!(err, SnapshotError::UnsupportedVersion(2)));
+    }
+
+    #[test]
+    fn test_rejects_non_monotonic_log() {
+        let raw = SNAPSHOT.replace(r#""index": 4"#, r#""index": 2"#);
+        let err = Snapshot::parse(&raw).unwrap_err();
+        assert!(matches!(
+            err,
+            SnapshotError::NonMonotonicLog {
+                previous: 2,
+                index: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn test_rejects_corrupt_json() {
+        let err = Snapshot::parse(&SNAPSHOT[..SNAPSHOT.len() / 2]).unwrap_err();
+        assert!(matches!(err, SnapshotError::Parse(_)));
+    }
+
+    #[test]
+    fn test_load_from_directory_and_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(SNAPSHOT_FILE_NAME);
+        std::fs::write(&file, SNAPSHOT).unwrap();
+
+        assert_eq!(
+            Snapshot::load(dir.path()).unwrap(),
+            Snapshot::load(&file).unwrap()
+        );
+        let err = Snapshot::load(&dir.path().join("missing.json")).unwrap_err();
+        assert!(matches!(err, SnapshotError::Io { .. }));
+    }
+}
@@ -0,0 +1,485 @@
+//! Where the dashboard's data comes from: live nodes, a recording, or nothing at all.
+
+use crate::cluster::{NodeLink, NodeSample};
+use crate::recording::{Playback, Record, Recorder};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// How often the background poller wakes up to check what is due.
+const POLL_TICK: Duration = Duration::from_millis(100);
+
+/// Batches the poller queues before it waits for the dashboard to catch up.
+const FEED_CAPACITY: usize = 64;
+
+/// One unit of collected data; also the payload of a recording line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Sample {
+    /// Dashboard-side event such as a reconnect, shown in the Log Explorer.
+    Event {
+        line: String,
+    },
+    ServerLogs {
+        lines: Vec<String>,
+    },
+    Cluster {
+        nodes: Vec<NodeSample>,
+    },
+    Deadlocks {
+        node: String,
+        cycles: Vec<String>,
+    },
+}
+
+/// Feeds the TUI loop; implemented by live polling and by replay.
+pub trait DataSource {
+    /// Returns the samples that became available since the last call.
+    async fn poll(&mut self) -> Vec<Sample>;
+
+    /// Whether samples describe the cluster right now; alerts only fire for live data.
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    /// Playback controls, for sources that replay a recording.
+    fn playback(&mut self) -> Option<&mut Playback> {
+        None
+    }
+
+    /// Position shown in the header while replaying.
+    fn timeline(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Source for offline snapshots: all data is loaded up front.
+pub struct Idle;
+
+impl DataSource for Idle {
+    async fn poll(&mut self) -> Vec<Sample> {
+        Vec::new()
+    }
+}
+
+/// Polls every watched node; the first one also feeds logs and deadlocks.
+pub struct LiveSource {
+    nodes: Vec<NodeLink>,
+    poll_interval: Duration,
+    deadlock_interval: Duration,
+    recorder: Option<Recorder>,
+}
+
+impl LiveSource {
+    pub fn new(
+        endpoints: &[String],
+        poll_interval: Duration,
+        deadlock_interval: Duration,
+        rpc_timeout: Duration,
+    ) -> Self {
+        Self {
+            nodes: endpoints
+                .iter()
+                .map(|endpoint| NodeLink::new(endpoint.clone(), rpc_timeout))
+                .collect(),
+            poll_interval,
+            deadlock_interval,
+            recorder: None,
+        }
+    }
+
+    pub fn with_recorder(mut self, recorder: Option<Recorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// Polls each node on its own task, so a dead or slow node only holds up its own row;
+    /// the returned feed hands over what they collect.
+    pub fn spawn(self) -> LiveFeed {
+        let (feed, samples) = mpsc::channel(FEED_CAPACITY);
+        let (reports, received) = mpsc::channel(FEED_CAPACITY);
+        let last_known = self.nodes.iter().map(NodeLink::sample).collect();
+
+        let mut tasks: Vec<_> = self
+            .nodes
+            .into_iter()
+            .enumerate()
+            .map(|(idx, link)| {
+                let poller = NodePoller {
+                    idx,
+                    link,
+                    poll_interval: self.poll_interval,
+                    next_poll: Instant::now(),
+                    primary: (idx == 0).then(|| PrimaryFeed {
+                        deadlock_interval: self.deadlock_interval,
+                        next_deadlock_check: Instant::now(),
+                        log_tail: LogTail::default(),
+                    }),
+                };
+                tokio::spawn(poller.run(reports.clone()))
+            })
+            .collect();
+        tasks.push(tokio::spawn(aggregate(
+            received,
+            last_known,
+            self.recorder,
+            feed,
+        )));
+        LiveFeed { samples, tasks }
+    }
+}
+
+/// What one node's poller collected in a tick, with the node's state afterwards.
+struct NodeReport {
+    idx: usize,
+    node: NodeSample,
+    samples: Vec<Sample>,
+}
+
+/// Polling loop of one node. Every call is bounded by `--rpc-timeout`.
+struct NodePoller {
+    idx: usize,
+    link: NodeLink,
+    poll_interval: Duration,
+    next_poll: Instant,
+    /// Set for the primary node only.
+    primary: Option<PrimaryFeed>,
+}
+
+impl NodePoller {
+    async fn run(mut self, reports: mpsc::Sender<NodeReport>) {
+        loop {
+            let samples = self.tick().await;
+            let report = NodeReport {
+                idx: self.idx,
+                node: self.link.sample(),
+                samples,
+            };
+            if reports.send(report).await.is_err() {
+                return;
+            }
+            tokio::time::sleep(POLL_TICK).await;
+        }
+    }
+
+    /// Makes whichever calls are due.
+    async fn tick(&mut self) -> Vec<Sample> {
+        let mut samples = Vec::new();
+        let event = |line| Sample::Event { line };
+
+        samples.extend(self.link.reconnect_if_due().await.map(event));
+        let now = Instant::now();
+        let status_due = now >= self.next_poll;
+        if status_due {
+            self.next_poll = now + self.poll_interval;
+            samples.extend(self.link.poll().await.map(event));
+        }
+        if let Some(primary) = &mut self.primary {
+            let deadlocks_due = now >= primary.next_deadlock_check;
+            if deadlocks_due {
+                primary.next_deadlock_check = now + primary.deadlock_interval;
+            }
+            primary
+                .fetch(&mut self.link, status_due, deadlocks_due, &mut samples)
+                .await;
+        }
+        samples
+    }
+}
+
+/// Schedule and state of what only the primary node feeds.
+struct PrimaryFeed {
+    deadlock_interval: Duration,
+    next_deadlock_check: Instant,
+    log_tail: LogTail,
+}
+
+impl PrimaryFeed {
+    /// Fetches recent logs and deadlocks with both calls in flight at once.
+    async fn fetch(
+        &mut self,
+        link: &mut NodeLink,
+        logs_due: bool,
+        deadlocks_due: bool,
+        samples: &mut Vec<Sample>,
+    ) {
+        let Some(client) = link.responsive_client_mut() else {
+            return;
+        };
+
+        // Clones share the connection, so neither call waits on the other
+        let mut logs_client = client.clone();
+        let (logs, deadlocks) = tokio::join!(
+            async {
+                if logs_due {
+                    Some(logs_client.recent_logs(5).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if deadlocks_due {
+                    Some(client.deadlocks().await)
+                } else {
+                    None
+                }
+            },
+        );
+        if let Some(Ok(lines)) = logs {
+            let lines = self.log_tail.fresh(lines);
+            if !lines.is_empty() {
+                samples.push(Sample::ServerLogs { lines });
+            }
+        }
+        if let Some(Ok(cycles)) = deadlocks {
+            samples.push(Sample::Deadlocks {
+                node: link.endpoint.clone(),
+                cycles,
+            });
+        }
+    }
+}
+
+/// Turns node reports into batches for the dashboard, recording them on the way.
+///
+/// `nodes` holds the last-known state of every node, so a report from one node is sent
+/// along with what the others last reported.
+async fn aggregate(
+    mut reports: mpsc::Receiver<NodeReport>,
+    mut nodes: Vec<NodeSample>,
+    mut recorder: Option<Recorder>,
+    feed: mpsc::Sender<Vec<Sample>>,
+) {
+    while let Some(report) = reports.recv().await {
+        let mut batch = report.samples;
+        if nodes[report.idx] != report.node {
+            nodes[report.idx] = report.node;
+            batch.push(Sample::Cluster {
+                nodes: nodes.clone(),
+            });
+        }
+        if batch.is_empty() {
+            continue;
+        }
+        if let Some(active) = &mut recorder
+            && let Err(e) = active.append(&batch)
+        {
+            // A full disk must not take the dashboard down with it
+            recorder = None;
+            batch.push(Sample::Event {
+                line: format!("Recording stopped: {}", e),
+            });
+        }
+        if feed.send(batch).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Dashboard end of the polling tasks started by [`LiveSource::spawn`].
+pub struct LiveFeed {
+    samples: mpsc::Receiver<Vec<Sample>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl DataSource for LiveFeed {
+    async fn poll(&mut self) -> Vec<Sample> {
+        let mut samples = Vec::new();
+        while let Ok(batch) = self.samples.try_recv() {
+            samples.extend(batch);
+        }
+        samples
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
+
+impl Drop for LiveFeed {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Drops the lines a `GetRecentLogs` poll repeats from the previous one.
+///
+/// Each poll returns the newest lines, oldest first, with no sequence number or
+/// timestamp. The longest tail of the previous batch that the new batch starts with
+/// is taken as already seen; identical lines logged after it are kept.
+#[derive(Debug, Default)]
+pub struct LogTail {
+    last: Vec<String>,
+}
+
+impl LogTail {
+    pub fn fresh(&mut self, batch: Vec<String>) -> Vec<String> {
+        let overlap = (1..=self.last.len().min(batch.len()))
+            .rev()
+            .find(|&len| self.last[self.last.len() - len..] == batch[..len])
+            .unwrap_or(0);
+        let fresh = batch[overlap..].to_vec();
+        self.last = batch;
+        fresh
+    }
+}
+
+/// Replays a `--record` file on a virtual clock.
+pub struct ReplaySource {
+    playback: Playback,
+    last_tick: Instant,
+}
+
+impl ReplaySource {
+    pub fn new(records: Vec<Record>) -> Self {
+        Self {
+            playback: Playback::new(records),
+            last_tick: Instant::now(),
+        }
+    }
+}
+
+impl DataSource for ReplaySource {
+    async fn poll(&mut self) -> Vec<Sample> {
+        let elapsed = self.last_tick.elapsed();
+        self.last_tick = Instant::now();
+        self.playback.advance(elapsed)
+    }
+
+    fn playback(&mut self) -> Option<&mut Playback> {
+        Some(&mut self.playback)
+    }
+
+    fn timeline(&self) -> Option<String> {
+        Some(self.playback.position())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{self, MockNode, strings};
+
+    #[test]
+    fn test_log_tail_skips_repeated_window() {
+        let mut tail = LogTail::default();
+        assert_eq!(tail.fresh(strings(&["a", "b", "c"])), ["a", "b", "c"]);
+        assert_eq!(tail.fresh(strings(&["a", "b", "c"])), Vec::<String>::new());
+        assert_eq!(tail.fresh(strings(&["c", "d", "e"])), ["d", "e"]);
+        // Nothing in common: more was logged between polls than one batch holds
+        assert_eq!(tail.fresh(strings(&["x", "y"])), ["x", "y"]);
+    }
+
+    #[test]
+    fn test_log_tail_keeps_identical_new_lines() {
+        let mut tail = LogTail::default();
+        tail.fresh(strings(&["retry", "ok"]));
+        assert_eq!(
+            tail.fresh(strings(&["ok", "retry", "retry"])),
+            ["retry", "retry"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_primary_logs_and_deadlocks_are_fetched_together() {
+        let delay = Duration::from_millis(300);
+        let address = testing::serve(MockNode {
+            logs: strings(&["INFO started"]),
+            deadlocks: strings(&["a -> b -> a"]),
+            read_delay: Some(delay),
+            ..MockNode::leader(1)
+        })
+        .await;
+        let mut poller = NodePoller {
+            idx: 0,
+            link: NodeLink::new(address, Duration::from_secs(2)),
+            poll_interval: Duration::from_secs(60),
+            next_poll: Instant::now(),
+            primary: Some(PrimaryFeed {
+                deadlock_interval: Duration::from_secs(60),
+                next_deadlock_check: Instant::now(),
+                log_tail: LogTail::default(),
+            }),
+        };
+
+        let started = Instant::now();
+        let samples = poller.tick().await;
+        assert!(
+            started.elapsed() < delay * 2,
+            "took {:?}",
+            started.elapsed()
+        );
+        assert!(
+            samples
+                .iter()
+                .any(|s| matches!(s, Sample::ServerLogs { .. }))
+        );
+        assert!(
+            samples
+                .iter()
+                .any(|s| matches!(s, Sample::Deadlocks { .. }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_feed_delivers_samples_from_background_task() {
+        let address = testing::serve(MockNode {
+            logs: strings(&["INFO started"]),
+            ..MockNode::leader(1)
+        })
+        .await;
+        let source = LiveSource::new(
+            &[address],
+            Duration::from_millis(50),
+            Duration::from_millis(50),
+            Duration::from_secs(2),
+        );
+        let mut feed = source.spawn();
+
+        let mut samples = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !samples
+            .iter()
+            .any(|s| matches!(s, Sample::ServerLogs { .. }))
+        {
+            assert!(Instant::now() < deadline, "no logs from {:?}", samples);
+            samples.extend(feed.poll().await);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(feed.is_live());
+        assert!(samples.iter().any(|s| matches!(s, Sample::Cluster { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_dead_node_does_not_hold_up_the_others() {
+        let healthy = testing::serve(MockNode::leader(1)).await;
+        let hung = testing::serve(MockNode {
+            stall: Some(Duration::from_secs(30)),
+            ..MockNode::leader(2)
+        })
+        .await;
+        let source = LiveSource::new(
+            &[hung, healthy],
+            Duration::from_millis(50),
+            Duration::from_millis(50),
+            Duration::from_secs(5),
+        );
+        let mut feed = source.spawn();
+
+        // Well within the hung node's 5s heartbeat timeout
+        let deadline = Instant::now() + Duration::from_secs(2);
+        loop {
+            assert!(Instant::now() < deadline, "healthy node never reported");
+            let answered = feed.poll().await.into_iter().any(
+                |sample| matches!(sample, Sample::Cluster { nodes } if nodes[1].status.is_some()),
+            );
+            if answered {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+}
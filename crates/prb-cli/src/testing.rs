@@ -0,0 +1,121 @@
+//! Shared test fixtures, including an in-process control plane for tests that exercise
+//! the gRPC client end to end.
+
+use crate::app::ConnectionStatus;
+use crate::client;
+use crate::cluster::NodeSample;
+use praborrow_lease::grpc::proto::control_plane_server::{ControlPlane, ControlPlaneServer};
+use praborrow_lease::grpc::proto::{DeadlockResponse, Empty, LogRequest, LogResponse, NodeStatus};
+use std::time::Duration;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+
+/// Answers every baseline control-plane RPC from fixed data.
+#[derive(Debug, Clone, Default)]
+pub struct MockNode {
+    pub id: u64,
+    pub state: String,
+    pub current_term: u64,
+    pub commit_index: u64,
+    pub logs: Vec<String>,
+    pub deadlocks: Vec<String>,
+    /// Delay before answering `GetNodeStatus`, to simulate a hung node.
+    pub stall: Option<Duration>,
+    /// Delay before answering `GetRecentLogs` and `GetDeadlocks`.
+    pub read_delay: Option<Duration>,
+}
+
+impl MockNode {
+    pub fn leader(id: u64) -> Self {
+        Self {
+            id,
+            state: "Leader".to_string(),
+            current_term: 7,
+            commit_index: 42,
+            ..Default::default()
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl ControlPlane for MockNode {
+    async fn get_node_status(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<NodeStatus>, Status> {
+        if let Some(stall) = self.stall {
+            tokio::time::sleep(stall).await;
+        }
+        Ok(Response::new(NodeStatus {
+            id: self.id,
+            state: self.state.clone(),
+            current_term: self.current_term,
+            commit_index: self.commit_index,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_recent_logs(
+        &self,
+        request: Request<LogRequest>,
+    ) -> Result<Response<LogResponse>, Status> {
+        if let Some(delay) = self.read_delay {
+            tokio::time::sleep(delay).await;
+        }
+        let limit = request.into_inner().limit as usize;
+        Ok(Response::new(LogResponse {
+            logs: self.logs.iter().take(limit).cloned().collect(),
+        }))
+    }
+
+    async fn get_deadlocks(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<DeadlockResponse>, Status> {
+        if let Some(delay) = self.read_delay {
+            tokio::time::sleep(delay).await;
+        }
+        Ok(Response::new(DeadlockResponse {
+            deadlocks: self.deadlocks.clone(),
+        }))
+    }
+}
+
+/// Serves `node` on an ephemeral local port and returns its `host:port`.
+pub async fn serve(node: MockNode) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(ControlPlaneServer::new(node))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    addr.to_string()
+}
+
+/// `host:port` nothing listens on.
+pub fn dead_address() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+/// Owned copies of `items`, for the APIs that take log lines or cycles as `&[String]`.
+pub fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+/// A connected node that last reported `state` in `term`.
+pub fn node_sample(endpoint: &str, node_id: u64, state: &str, term: u64) -> NodeSample {
+    NodeSample {
+        endpoint: endpoint.to_string(),
+        connection: ConnectionStatus::Connected,
+        status: Some(client::NodeStatus {
+            node_id,
+            state: state.to_string(),
+            current_term: term,
+            commit_index: 0,
+        }),
+        missed_polls: 0,
+        last_seen_ms: None,
+    }
+}
@@ -0,0 +1,213 @@
+//! Every style the dashboard draws with, so terminals with light backgrounds and
+//! `NO_COLOR` users get a readable screen.
+
+use clap::ValueEnum;
+use ratatui::style::{Color, Modifier, Style};
+
+/// `--theme` / `PRB_THEME` presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub connected: Style,
+    pub reconnecting: Style,
+    pub disconnected: Style,
+    pub offline: Style,
+    /// The two phases of the flashing alert segment in the header.
+    pub alert_on: Style,
+    pub alert_off: Style,
+    pub tabs: Style,
+    pub tab_selected: Style,
+    pub banner: Style,
+    pub footer: Style,
+    /// Table headers and help sections.
+    pub heading: Style,
+    pub key: Style,
+    pub hint: Style,
+    /// Borders of deadlock panels.
+    pub danger_border: Style,
+    /// Split-brain rows and deadlock cycle edges.
+    pub critical: Style,
+    /// Unreachable nodes and context edges.
+    pub muted: Style,
+    pub healthy: Style,
+    pub unhealthy: Style,
+    pub deadlock: Style,
+    pub filter_match: Style,
+    pub selection: Style,
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self::dark(),
+            ThemeName::Light => Self::light(),
+            ThemeName::HighContrast => Self::high_contrast(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        Self {
+            connected: bold.fg(Color::Green),
+            reconnecting: bold.fg(Color::Yellow),
+            disconnected: bold.fg(Color::Red),
+            offline: bold.fg(Color::Gray),
+            alert_on: Style::default().fg(Color::White).bg(Color::Red),
+            alert_off: Style::default().fg(Color::Red),
+            tabs: Style::default().fg(Color::White),
+            tab_selected: bold.fg(Color::Yellow),
+            banner: Style::default().fg(Color::White).bg(Color::Red),
+            footer: Style::default().fg(Color::Gray),
+            heading: bold,
+            key: Style::default().fg(Color::Cyan),
+            hint: Style::default().fg(Color::Gray),
+            danger_border: Style::default().fg(Color::Red),
+            critical: bold.fg(Color::Red),
+            muted: Style::default().fg(Color::DarkGray),
+            healthy: Style::default().fg(Color::Green),
+            unhealthy: Style::default().fg(Color::Red),
+            deadlock: Style::default().fg(Color::LightRed),
+            filter_match: bold.fg(Color::Black).bg(Color::Yellow),
+            selection: Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// Darker 256-color shades that stay legible on white backgrounds.
+    pub fn light() -> Self {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let green = Color::Indexed(22);
+        let amber = Color::Indexed(130);
+        let red = Color::Indexed(124);
+        let blue = Color::Indexed(18);
+        let grey = Color::Indexed(242);
+        Self {
+            connected: bold.fg(green),
+            reconnecting: bold.fg(amber),
+            disconnected: bold.fg(red),
+            offline: bold.fg(grey),
+            alert_on: Style::default().fg(Color::White).bg(red),
+            alert_off: Style::default().fg(red),
+            tabs: Style::default().fg(Color::Black),
+            tab_selected: bold.fg(blue),
+            banner: Style::default().fg(Color::White).bg(red),
+            footer: Style::default().fg(grey),
+            heading: bold,
+            key: Style::default().fg(blue),
+            hint: Style::default().fg(grey),
+            danger_border: Style::default().fg(red),
+            critical: bold.fg(red),
+            muted: Style::default().fg(grey),
+            healthy: Style::default().fg(green),
+            unhealthy: Style::default().fg(red),
+            deadlock: Style::default().fg(red),
+            filter_match: bold.fg(Color::Black).bg(Color::Indexed(228)),
+            selection: Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// Bold text and solid backgrounds instead of subtle hues.
+    pub fn high_contrast() -> Self {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let on = |fg, bg| bold.fg(fg).bg(bg);
+        Self {
+            connected: on(Color::Black, Color::LightGreen),
+            reconnecting: on(Color::Black, Color::LightYellow),
+            disconnected: on(Color::White, Color::Red),
+            offline: on(Color::Black, Color::White),
+            alert_on: on(Color::White, Color::Red),
+            alert_off: on(Color::Black, Color::White),
+            tabs: bold.fg(Color::White),
+            tab_selected: on(Color::Black, Color::LightYellow),
+            banner: on(Color::White, Color::Red),
+            footer: bold.fg(Color::White),
+            heading: bold.add_modifier(Modifier::UNDERLINED),
+            key: bold.fg(Color::LightCyan),
+            hint: bold.fg(Color::White),
+            danger_border: bold.fg(Color::LightRed),
+            critical: on(Color::White, Color::Red),
+            muted: Style::default().fg(Color::Gray),
+            healthy: bold.fg(Color::LightGreen),
+            unhealthy: bold.fg(Color::LightRed),
+            deadlock: bold.fg(Color::LightRed),
+            filter_match: on(Color::Black, Color::LightYellow),
+            selection: bold.add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// Drops every color, keeping bold/reverse so selections stay visible.
+    pub fn without_color(self) -> Self {
+        self.map(|style| Style {
+            fg: None,
+            bg: None,
+            ..style
+        })
+    }
+
+    fn map(self, f: impl Fn(Style) -> Style) -> Self {
+        Self {
+            connected: f(self.connected),
+            reconnecting: f(self.reconnecting),
+            disconnected: f(self.disconnected),
+            offline: f(self.offline),
+            alert_on: f(self.alert_on),
+            alert_off: f(self.alert_off),
+            tabs: f(self.tabs),
+            tab_selected: f(self.tab_selected),
+            banner: f(self.banner),
+            footer: f(self.footer),
+            heading: f(self.heading),
+            key: f(self.key),
+            hint: f(self.hint),
+            danger_border: f(self.danger_border),
+            critical: f(self.critical),
+            muted: f(self.muted),
+            healthy: f(self.healthy),
+            unhealthy: f(self.unhealthy),
+            deadlock: f(self.deadlock),
+            filter_match: f(self.filter_match),
+            selection: f(self.selection),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Honors the `NO_COLOR` convention: any non-empty value disables color.
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_color_keeps_modifiers() {
+        for name in [ThemeName::Dark, ThemeName::Light, ThemeName::HighContrast] {
+            let theme = Theme::new(name);
+            let plain = theme.without_color();
+            assert_eq!(plain.connected.fg, None);
+            assert_eq!(plain.banner.bg, None);
+            assert_eq!(plain.selection.add_modifier, theme.selection.add_modifier);
+            assert_eq!(plain.heading.add_modifier, theme.heading.add_modifier);
+        }
+    }
+
+    #[test]
+    fn test_presets_differ() {
+        assert_eq!(Theme::default(), Theme::dark());
+        assert_ne!(Theme::dark().connected, Theme::light().connected);
+        assert_ne!(Theme::dark().connected, Theme::high_contrast().connected);
+    }
+}
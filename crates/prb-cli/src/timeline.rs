@@ -0,0 +1,255 @@
+//! Deadlock history behind the Timeline tab, built up from successive `GetDeadlocks` polls.
+//!
+//! The control plane only reports the cycles that exist right now, so the dashboard
+//! remembers when each cycle first appeared, when it was last reported and when a poll
+//! stopped reporting it.
+
+use crate::graph::parse_cycle;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Default `--timeline-horizon`: the axis spans the last 15 minutes.
+pub const DEFAULT_HORIZON_SECS: u64 = 15 * 60;
+
+/// Incidents kept; the oldest are dropped first.
+pub const MAX_INCIDENTS: usize = 500;
+
+/// Windows counted by the summary line above the bars.
+pub const SUMMARY_WINDOWS: [Duration; 3] = [
+    Duration::from_secs(60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(15 * 60),
+];
+
+/// One deadlock cycle, from the first poll that reported it to the first that did not.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Incident {
+    /// Cycle as the server describes it, e.g. `a -> b -> a`.
+    pub cycle: String,
+    /// Unix time in milliseconds.
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+    /// `None` while the cycle is still reported.
+    pub cleared_at_ms: Option<u64>,
+}
+
+impl Incident {
+    /// An incident known from a single observation, e.g. one poll or a snapshot entry.
+    pub fn detected(cycle: String, at_ms: u64) -> Self {
+        Self {
+            cycle,
+            first_seen_ms: at_ms,
+            last_seen_ms: at_ms,
+            cleared_at_ms: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.cleared_at_ms.is_none()
+    }
+
+    pub fn involves(&self, resource_id: &str) -> bool {
+        parse_cycle(&self.cycle)
+            .iter()
+            .any(|node| node == resource_id)
+    }
+
+    /// Where the incident's bar ends: when it cleared, or `now_ms` while it persists.
+    pub fn end_ms(&self, now_ms: u64) -> u64 {
+        self.cleared_at_ms
+            .unwrap_or_else(|| now_ms.max(self.last_seen_ms))
+    }
+}
+
+/// Every incident seen since the dashboard started, oldest first.
+#[derive(Debug, Default)]
+pub struct DeadlockHistory {
+    incidents: VecDeque<Incident>,
+}
+
+impl DeadlockHistory {
+    /// Folds in the cycles one poll reported at `at_ms`.
+    ///
+    /// Cycles that are still reported extend their incident, new ones open an incident
+    /// and open incidents missing from the poll are closed.
+    pub fn observe(&mut self, cycles: &[String], at_ms: u64) {
+        for incident in self.incidents.iter_mut().filter(|i| i.is_active()) {
+            if cycles.contains(&incident.cycle) {
+                incident.last_seen_ms = at_ms;
+            } else {
+                incident.cleared_at_ms = Some(at_ms);
+            }
+        }
+        for cycle in cycles {
+            let open = self
+                .incidents
+                .iter()
+                .any(|incident| incident.is_active() && &incident.cycle == cycle);
+            if !open {
+                self.incidents
+                    .push_back(Incident::detected(cycle.clone(), at_ms));
+            }
+        }
+        while self.incidents.len() > MAX_INCIDENTS {
+            self.incidents.pop_front();
+        }
+    }
+
+    pub fn incidents(&self) -> &VecDeque<Incident> {
+        &self.incidents
+    }
+
+    pub fn len(&self) -> usize {
+        self.incidents.len()
+    }
+}
+
+/// Columns `(offset, len)` an incident covers on an axis `width` columns wide that ends
+/// at `now_ms` and spans `horizon`.
+///
+/// Incidents over before the axis starts get `None`; any visible one gets at least a
+/// column so a single poll still shows up.
+pub fn bar_span(
+    start_ms: u64,
+    end_ms: u64,
+    now_ms: u64,
+    horizon: Duration,
+    width: u16,
+) -> Option<(u16, u16)> {
+    let horizon_ms = horizon.as_millis() as u64;
+    let axis_start = now_ms.saturating_sub(horizon_ms);
+    if horizon_ms == 0 || width == 0 || end_ms < axis_start {
+        return None;
+    }
+    let column = |ms: u64| (ms.clamp(axis_start, now_ms) - axis_start) * width as u64 / horizon_ms;
+    let offset = column(start_ms).min(width as u64 - 1);
+    let len = column(end_ms).saturating_sub(offset).max(1);
+    Some((offset as u16, len.min(width as u64 - offset) as u16))
+}
+
+/// Number of incidents still ongoing at some point within each of `windows`.
+pub fn seen_within<'a, const N: usize>(
+    incidents: impl IntoIterator<Item = &'a Incident> + Clone,
+    now_ms: u64,
+    windows: [Duration; N],
+) -> [usize; N] {
+    windows.map(|window| {
+        let since = now_ms.saturating_sub(window.as_millis() as u64);
+        incidents
+            .clone()
+            .into_iter()
+            .filter(|incident| incident.end_ms(now_ms) >= since)
+            .count()
+    })
+}
+
+/// Compact duration for axis labels, e.g. `15m` or `90s`.
+pub fn format_span(span: Duration) -> String {
+    let secs = span.as_secs();
+    if secs >= 3600 && secs.is_multiple_of(3600) {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 && secs.is_multiple_of(60) {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::strings;
+
+    #[test]
+    fn test_observe_tracks_incident_lifetimes() {
+        let mut history = DeadlockHistory::default();
+        history.observe(&strings(&["a -> b -> a"]), 1_000);
+        history.observe(&strings(&["a -> b -> a", "c -> d -> c"]), 2_000);
+        history.observe(&strings(&["c -> d -> c"]), 3_000);
+        // The same cycle coming back is a new incident
+        history.observe(&strings(&["a -> b -> a"]), 4_000);
+
+        let incidents: Vec<_> = history
+            .incidents()
+            .iter()
+            .map(|i| {
+                (
+                    i.cycle.as_str(),
+                    i.first_seen_ms,
+                    i.last_seen_ms,
+                    i.cleared_at_ms,
+                )
+            })
+            .collect();
+        assert_eq!(
+            incidents,
+            [
+                ("a -> b -> a", 1_000, 2_000, Some(3_000)),
+                ("c -> d -> c", 2_000, 3_000, Some(4_000)),
+                ("a -> b -> a", 4_000, 4_000, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_observe_is_bounded() {
+        let mut history = DeadlockHistory::default();
+        for at in 0..(MAX_INCIDENTS as u64 + 10) {
+            history.observe(&strings(&[&format!("{} -> x -> {}", at, at)]), at);
+        }
+        assert_eq!(history.len(), MAX_INCIDENTS);
+        assert_eq!(history.incidents()[0].first_seen_ms, 10);
+    }
+
+    #[test]
+    fn test_involves() {
+        let incident = Incident::detected("db -> cache -> db".to_string(), 0);
+        assert!(incident.involves("cache"));
+        assert!(!incident.involves("queue"));
+        assert!(incident.is_active());
+        assert_eq!(incident.end_ms(50), 50);
+    }
+
+    #[test]
+    fn test_bar_span() {
+        let horizon = Duration::from_secs(100);
+        let now = 1_000_000;
+        // Axis covers 900_000..=1_000_000 over 40 columns
+        assert_eq!(bar_span(950_000, now, now, horizon, 40), Some((20, 20)));
+        assert_eq!(bar_span(0, now, now, horizon, 40), Some((0, 40)));
+        assert_eq!(bar_span(910_000, 930_000, now, horizon, 40), Some((4, 8)));
+        // A single poll keeps one column, even at the right edge
+        assert_eq!(bar_span(now, now, now, horizon, 40), Some((39, 1)));
+        assert_eq!(bar_span(0, 899_999, now, horizon, 40), None);
+        assert_eq!(bar_span(950_000, now, now, Duration::ZERO, 40), None);
+        assert_eq!(bar_span(950_000, now, now, horizon, 0), None);
+    }
+
+    #[test]
+    fn test_seen_within_buckets() {
+        let now = 10 * 60_000;
+        let mut cleared = Incident::detected("a -> b -> a".to_string(), 0);
+        cleared.cleared_at_ms = Some(now - 2 * 60_000);
+        let incidents = [
+            Incident::detected("c -> d -> c".to_string(), now - 30_000),
+            cleared,
+            Incident {
+                cleared_at_ms: Some(0),
+                ..Incident::detected("e -> f -> e".to_string(), 0)
+            },
+        ];
+        assert_eq!(seen_within(&incidents, now, SUMMARY_WINDOWS), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_format_span() {
+        assert_eq!(
+            format_span(Duration::from_secs(DEFAULT_HORIZON_SECS)),
+            "15m"
+        );
+        assert_eq!(format_span(Duration::from_secs(7200)), "2h");
+        assert_eq!(format_span(Duration::from_secs(5400)), "90m");
+        assert_eq!(format_span(Duration::from_secs(90)), "90s");
+    }
+}
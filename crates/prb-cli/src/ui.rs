@@ -0,0 +1,837 @@
+//! Dashboard rendering. Every style comes from the active [`Theme`].
+
+use crate::Mode;
+use crate::alerts::FLASH_DURATION;
+use crate::app::{App, ConnectionStatus, DEADLOCKS_TAB, TAB_TITLES};
+use crate::client::NodeStatus;
+use crate::cluster::{ClusterView, NodeSample};
+use crate::filter::LogFilter;
+use crate::graph::{self, WaitGraph};
+use crate::inspect::{self, ResourceReport};
+use crate::keymap;
+use crate::theme::Theme;
+use crate::timeline::{self, Incident, SUMMARY_WINDOWS};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, Tabs,
+    },
+};
+use std::time::Duration;
+
+/// Smallest terminal the dashboard is laid out in; anything smaller gets a notice.
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Dashboard,
+    TooSmall,
+}
+
+/// Picks what to draw for a terminal of size `area`.
+pub fn select_screen(area: Rect) -> Screen {
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        Screen::TooSmall
+    } else {
+        Screen::Dashboard
+    }
+}
+
+/// A `width` x `height` rectangle centered in `area`, shrunk to fit.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+pub fn ui(frame: &mut ratatui::Frame, app: &App) {
+    let theme = &app.theme;
+    if select_screen(frame.area()) == Screen::TooSmall {
+        let message = format!("window too small (need {}x{})", MIN_WIDTH, MIN_HEIGHT);
+        let notice = Paragraph::new(message)
+            .style(theme.hint)
+            .alignment(Alignment::Center);
+        frame.render_widget(notice, centered(frame.area(), frame.area().width, 1));
+        return;
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Main content
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    // Header
+    let (status_text, status_style) = match &app.connection_status {
+        ConnectionStatus::Connected => ("CONNECTED", theme.connected),
+        ConnectionStatus::Reconnecting(_retry) => {
+            // Can't return string with lifetime issues here easily, let's format later or use fixed strings
+            // Actually let's use a Cow or just format the whole header line
+            ("RECONNECTING", theme.reconnecting)
+        }
+        ConnectionStatus::Disconnected(_) => ("DISCONNECTED", theme.disconnected),
+        ConnectionStatus::OfflineMode => ("OFFLINE", theme.offline),
+    };
+
+    let mode_str = match &app.mode {
+        Mode::Online { addresses } => match addresses.as_slice() {
+            [address] => format!("Online: {}", address),
+            _ => format!("Online: {} nodes", addresses.len()),
+        },
+        Mode::Offline { path } => format!("Offline: {:?}", path),
+        Mode::Replay { path } => format!("Replay: {:?}", path),
+    };
+
+    let time_status = match app.new_events() {
+        _ if !app.paused => "RUNNING".to_string(),
+        0 => "PAUSED".to_string(),
+        n => format!("PAUSED ({} new events)", n),
+    };
+
+    let header_text = format!(
+        "PraBorrow Dashboard - {} | Status: {} | {}",
+        mode_str, status_text, time_status
+    );
+
+    // Offline snapshots show where the view is scrubbed to
+    let header_text = match (&app.snapshot, app.scrub_index) {
+        (Some(snapshot), Some(index)) => format!(
+            "{} | Log index {}/{}",
+            header_text,
+            index,
+            snapshot.last_index().unwrap_or(0)
+        ),
+        _ => header_text,
+    };
+
+    let header_text = match &app.timeline {
+        Some(timeline) => format!("{} | {}", header_text, timeline),
+        None => header_text,
+    };
+
+    // For Reconnecting, maybe append retry count
+    let header_text = if let ConnectionStatus::Reconnecting(secs) = app.connection_status {
+        format!("{} (Backoff {}s)", header_text, secs)
+    } else {
+        header_text
+    };
+    let header_text = match app.cluster.missed_polls() {
+        0 => header_text,
+        missed => format!("{} | Missed polls: {}", header_text, missed),
+    };
+
+    let mut header_spans = vec![Span::raw(header_text)];
+    if let Some((message, raised_at)) = &app.alert_flash
+        && raised_at.elapsed() < FLASH_DURATION
+    {
+        // Alternate every 500ms; the 250ms redraw cadence drives the flashing
+        let style = if (raised_at.elapsed().as_millis() / 500) % 2 == 0 {
+            theme.alert_on
+        } else {
+            theme.alert_off
+        };
+        header_spans.push(Span::raw(" | "));
+        header_spans.push(Span::styled(message.clone(), style));
+    }
+
+    let header = Paragraph::new(Line::from(header_spans))
+        .style(status_style)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, chunks[0]);
+
+    // Main Content (Tabs)
+    let titles: Vec<Line> = TAB_TITLES.iter().cloned().map(Line::from).collect();
+
+    let tabs = Tabs::new(titles)
+        .select(app.tab_index)
+        .block(Block::default().title("View").borders(Borders::ALL))
+        .style(theme.tabs)
+        .highlight_style(theme.tab_selected);
+
+    // Split main area into error banner, tabs and content
+    let banner_height = if app.banner.is_some() { 3 } else { 0 };
+    let main_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(banner_height),
+            Constraint::Length(3),
+            Constraint::Min(3),
+        ])
+        .split(chunks[1]);
+
+    if let Some(banner) = &app.banner {
+        let banner = Paragraph::new(banner.as_str())
+            .style(theme.banner)
+            .block(Block::default().title("Error").borders(Borders::ALL));
+        frame.render_widget(banner, main_chunks[0]);
+    }
+
+    frame.render_widget(tabs, main_chunks[1]);
+
+    match app.tab_index {
+        0 => render_overview(frame, main_chunks[2], app, theme),
+        1 => render_log_explorer(frame, main_chunks[2], app, theme),
+        2 => render_deadlocks(frame, main_chunks[2], app, theme),
+        3 => render_timeline(frame, main_chunks[2], app, theme),
+        _ => {}
+    }
+
+    // Footer
+    let footer_text = if app.is_typing {
+        format!("Filter: {}_", app.filter_input)
+    } else if !app.filter_input.is_empty() {
+        format!(
+            "Filter: {} (Press '/' to edit, 'Esc' to clear) | 'p' Pause | 'q' Quit",
+            app.filter_input
+        )
+    } else {
+        "Press '?' for help, '/' to filter, 'p' to pause, 'q' to quit".to_string()
+    };
+    let footer_text = match &app.filter_error {
+        Some(error) => format!("Filter error: {} | {}", error, footer_text),
+        None => footer_text,
+    };
+    let footer_text = match &app.notice {
+        Some(notice) => format!("{} | {}", notice, footer_text),
+        None => footer_text,
+    };
+    let footer_text = if app.tab_index == DEADLOCKS_TAB && !app.deadlocks.is_empty() {
+        format!("{} | 'i' Inspect", footer_text)
+    } else {
+        footer_text
+    };
+    let footer_text = if app.snapshot.is_some() {
+        format!("{} | '[' ']' Scrub log", footer_text)
+    } else {
+        footer_text
+    };
+
+    let footer = Paragraph::new(footer_text)
+        .style(theme.footer)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[2]);
+
+    if let Some(report) = &app.inspection {
+        render_inspection(frame, report, theme);
+    }
+    if app.show_help {
+        render_help(frame, app, theme);
+    }
+}
+
+/// Modal list of every active binding, grouped by the tab it applies to.
+fn render_help(frame: &mut ratatui::Frame, app: &App, theme: &Theme) {
+    let bindings = app.keymap.bindings();
+    let mut lines = Vec::new();
+    let scopes = std::iter::once(None).chain((0..TAB_TITLES.len()).map(Some));
+    for scope in scopes {
+        let in_scope: Vec<_> = bindings.iter().filter(|b| b.tab == scope).collect();
+        if in_scope.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            keymap::scope_label(scope),
+            theme.heading,
+        )));
+        for binding in in_scope {
+            let keys: String = binding.keys.iter().map(|k| key_label(*k)).collect();
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<10}", keys), theme.key),
+                Span::raw(binding.help),
+            ]));
+        }
+    }
+
+    let popup = centered(frame.area(), 56, lines.len() as u16 + 2);
+    let help = Paragraph::new(lines).block(
+        Block::default()
+            .title("Key Bindings (Esc to close)")
+            .borders(Borders::ALL),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(help, popup);
+}
+
+fn render_inspection(frame: &mut ratatui::Frame, report: &ResourceReport, theme: &Theme) {
+    let mut lines = Vec::new();
+    if report.events.is_empty() {
+        lines.push(Line::styled("No deadlocks observed.", theme.muted));
+    }
+    for event in &report.events {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}  ", inspect::format_utc(event.at_ms)),
+                theme.hint,
+            ),
+            Span::styled("DEADLOCK ", theme.critical),
+            Span::raw(event.summary.clone()),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled("Recent logs", theme.heading));
+    if report.log_lines.is_empty() {
+        lines.push(Line::styled("No recent log line mentions it.", theme.muted));
+    }
+    lines.extend(
+        report
+            .log_lines
+            .iter()
+            .map(|line| Line::raw(format!("  {}", line))),
+    );
+
+    let popup = centered(frame.area(), 96, lines.len() as u16 + 2);
+    let account = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!("{} (Esc to close)", report.resource_id))
+            .borders(Borders::ALL),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(account, popup);
+}
+
+fn key_label(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::BackTab => "S-Tab".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn render_overview(
+    frame: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    app: &App,
+    theme: &Theme,
+) {
+    if let Some(snapshot) = &app.snapshot {
+        let node = snapshot
+            .node_id
+            .map_or_else(|| "unknown".to_string(), |id| id.to_string());
+        let text = vec![
+            Line::from(Span::raw(format!("Snapshot of node: {}", node))),
+            Line::from(Span::raw(format!("Taken at: {} ms", snapshot.taken_at_ms))),
+            Line::from(Span::raw(format!("Log entries: {}", snapshot.log.len()))),
+            Line::from(Span::raw(format!("Leases: {}", snapshot.leases.len()))),
+            Line::from(Span::raw(format!(
+                "Deadlock incidents: {}",
+                snapshot.deadlocks.len()
+            ))),
+        ];
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .title("Snapshot Overview")
+                .borders(Borders::ALL),
+        );
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    render_cluster(frame, area, &app.cluster, theme);
+}
+
+/// Per-node table; rows that disagree with the rest of the cluster are drawn in red.
+/// Connection column of the Overview table; a node that stopped answering shows when it last did.
+fn connection_label(node: &NodeSample) -> String {
+    let connection = match &node.connection {
+        ConnectionStatus::Connected if node.missed_polls > 0 => {
+            format!("missed {} polls", node.missed_polls)
+        }
+        ConnectionStatus::Connected => return "connected".to_string(),
+        ConnectionStatus::Reconnecting(secs) => format!("reconnecting ({}s)", secs),
+        ConnectionStatus::Disconnected(_) => "disconnected".to_string(),
+        ConnectionStatus::OfflineMode => return "offline".to_string(),
+    };
+    match node.last_seen_ms {
+        Some(ms) => format!(
+            "{} (last seen {})",
+            connection,
+            &inspect::format_utc(ms)[11..]
+        ),
+        None => connection,
+    }
+}
+
+fn render_cluster(
+    frame: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    cluster: &ClusterView,
+    theme: &Theme,
+) {
+    let header = Row::new(vec![
+        "Node",
+        "Endpoint",
+        "Role",
+        "Term",
+        "Commit",
+        "Connection",
+    ])
+    .style(theme.heading);
+
+    let rows: Vec<Row> = cluster
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| {
+            let disagreement = cluster.disagreement(idx);
+            let field = |f: fn(&NodeStatus) -> String| {
+                node.status.as_ref().map_or_else(|| "-".to_string(), f)
+            };
+            let role = match disagreement {
+                Some(d) => format!("{} ({})", field(|s| s.state.clone()), d.label()),
+                None => field(|s| s.state.clone()),
+            };
+            let style = match (&node.connection, disagreement) {
+                (_, Some(_)) => theme.critical,
+                (ConnectionStatus::Connected, None) => Style::default(),
+                _ => theme.muted,
+            };
+            Row::new(vec![
+                Cell::from(field(|s| s.node_id.to_string())),
+                Cell::from(node.endpoint.clone()),
+                Cell::from(role),
+                Cell::from(field(|s| s.current_term.to_string())),
+                Cell::from(field(|s| s.commit_index.to_string())),
+                Cell::from(connection_label(node)),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let title_style = if cluster.is_healthy() {
+        theme.healthy
+    } else {
+        theme.unhealthy
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Percentage(30),
+            Constraint::Length(30),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Min(12),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(Span::styled(
+                format!("Cluster Overview - {}", cluster.summary()),
+                title_style,
+            ))
+            .borders(Borders::ALL),
+    );
+    frame.render_widget(table, area);
+}
+
+fn render_log_explorer(
+    frame: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    app: &App,
+    theme: &Theme,
+) {
+    let visible = app.visible_logs();
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|log| ListItem::new(highlight_line(log, &app.filter, theme)))
+        .collect();
+
+    let title = if app.log_view.is_following() {
+        "Raft Logs [FOLLOW]"
+    } else {
+        "Raft Logs [SCROLL - End/F to follow]"
+    };
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(theme.selection);
+    let mut state = ListState::default().with_selected(app.log_view.selected(visible.len()));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Splits `line` into spans, emphasizing the parts the filter matched.
+fn highlight_line<'a>(line: &'a str, filter: &LogFilter, theme: &Theme) -> Line<'a> {
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for range in filter.highlights(line) {
+        if range.start > cursor {
+            spans.push(Span::raw(&line[cursor..range.start]));
+        }
+        spans.push(Span::styled(&line[range.clone()], theme.filter_match));
+        cursor = range.end;
+    }
+    if cursor < line.len() {
+        spans.push(Span::raw(&line[cursor..]));
+    }
+    Line::from(spans)
+}
+
+fn render_deadlocks(
+    frame: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    app: &App,
+    theme: &Theme,
+) {
+    if app.deadlocks.is_empty() {
+        let paragraph = Paragraph::new(
+            "\n  No deadlocks detected in Sovereign resource graph.\n  System is healthy.",
+        )
+        .style(theme.healthy)
+        .block(
+            Block::default()
+                .title("Deadlock Detector")
+                .borders(Borders::ALL),
+        );
+        frame.render_widget(paragraph, area);
+    } else {
+        let items: Vec<ListItem> = app
+            .deadlocks
+            .iter()
+            .map(|d| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(" ⚠ ", theme.critical),
+                    Span::raw(d),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title("DEADLOCKS DETECTED")
+                    .borders(Borders::ALL)
+                    .border_style(theme.danger_border),
+            )
+            .style(theme.deadlock)
+            .highlight_style(theme.selection);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(area);
+
+        let mut state = ListState::default()
+            .with_selected(Some(app.deadlock_cursor.min(app.deadlocks.len() - 1)));
+        frame.render_stateful_widget(list, columns[0], &mut state);
+
+        let graph = WaitGraph::from_cycle_descriptions(&app.deadlocks);
+        let inner_width = columns[1].width.saturating_sub(2) as usize;
+        let lines: Vec<Line> = graph::layout(&graph, inner_width)
+            .into_iter()
+            .map(|line| {
+                Line::from(
+                    line.into_iter()
+                        .map(|segment| {
+                            let style = if segment.in_cycle {
+                                theme.critical
+                            } else {
+                                Style::default()
+                            };
+                            Span::styled(segment.text, style)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        let diagram = Paragraph::new(lines).block(
+            Block::default()
+                .title("Wait-For Graph")
+                .borders(Borders::ALL)
+                .border_style(theme.danger_border),
+        );
+        frame.render_widget(diagram, columns[1]);
+    }
+}
+
+/// Width of the cycle column left of the bars.
+const TIMELINE_CYCLE_WIDTH: usize = 28;
+
+fn render_timeline(
+    frame: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    app: &App,
+    theme: &Theme,
+) {
+    let incidents = app.timeline_incidents();
+    let now_ms = inspect::now_ms();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+
+    let counts = timeline::seen_within(incidents.iter().copied(), now_ms, SUMMARY_WINDOWS);
+    let mut summary = vec![Span::styled("Deadlocked within last ", theme.heading)];
+    for (idx, (window, count)) in SUMMARY_WINDOWS.iter().zip(counts).enumerate() {
+        if idx > 0 {
+            summary.push(Span::raw(" | "));
+        }
+        let style = if count > 0 {
+            theme.critical
+        } else {
+            Style::default()
+        };
+        summary.push(Span::raw(format!("{}: ", timeline::format_span(*window))));
+        summary.push(Span::styled(count.to_string(), style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(summary)), rows[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+        .split(rows[1]);
+
+    let title = format!(
+        "Deadlocks: -{} → now ({} incidents)",
+        timeline::format_span(app.timeline_horizon),
+        incidents.len()
+    );
+    if incidents.is_empty() {
+        let message = Paragraph::new("No deadlocks observed since the dashboard started.")
+            .style(theme.muted)
+            .block(Block::default().title(title).borders(Borders::ALL));
+        frame.render_widget(message, rows[1]);
+        return;
+    }
+
+    // Two border columns plus a space after the label
+    let axis_width = columns[0]
+        .width
+        .saturating_sub((TIMELINE_CYCLE_WIDTH + 3) as u16);
+    let items: Vec<ListItem> = incidents
+        .iter()
+        .map(|incident| {
+            let mut spans = vec![Span::raw(format!(
+                "{:<w$.w$} ",
+                incident.cycle,
+                w = TIMELINE_CYCLE_WIDTH
+            ))];
+            let span = timeline::bar_span(
+                incident.first_seen_ms,
+                incident.end_ms(now_ms),
+                now_ms,
+                app.timeline_horizon,
+                axis_width,
+            );
+            if let Some((offset, len)) = span {
+                let style = if incident.is_active() {
+                    theme.critical
+                } else {
+                    theme.muted
+                };
+                spans.push(Span::raw(" ".repeat(offset as usize)));
+                spans.push(Span::styled("█".repeat(len as usize), style));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(theme.selection)
+        .block(Block::default().title(title).borders(Borders::ALL));
+    let mut state =
+        ListState::default().with_selected(Some(app.timeline_cursor.min(incidents.len() - 1)));
+    frame.render_stateful_widget(list, columns[0], &mut state);
+
+    let selected = state.selected().and_then(|idx| incidents.get(idx).copied());
+    let details = Paragraph::new(incident_details(selected, now_ms, theme))
+        .block(Block::default().title("Incident").borders(Borders::ALL));
+    frame.render_widget(details, columns[1]);
+}
+
+fn incident_details<'a>(incident: Option<&Incident>, now_ms: u64, theme: &Theme) -> Vec<Line<'a>> {
+    let Some(incident) = incident else {
+        return vec![Line::styled("No incident selected", theme.muted)];
+    };
+    let field = |name: &'static str, value: Span<'a>| {
+        Line::from(vec![
+            Span::styled(format!("{:<11}", name), theme.heading),
+            value,
+        ])
+    };
+    let status = match incident.cleared_at_ms {
+        None => Span::styled("ongoing", theme.critical),
+        Some(at_ms) => Span::raw(format!("cleared {}", inspect::format_utc(at_ms))),
+    };
+    let lasted = Duration::from_millis(incident.end_ms(now_ms) - incident.first_seen_ms);
+    vec![
+        field("Cycle", Span::raw(incident.cycle.clone())),
+        field("Status", status),
+        field(
+            "First seen",
+            Span::raw(inspect::format_utc(incident.first_seen_ms)),
+        ),
+        field(
+            "Last seen",
+            Span::raw(inspect::format_utc(incident.last_seen_ms)),
+        ),
+        field("Lasted", Span::raw(timeline::format_span(lasted))),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::node_sample;
+    use crate::theme::ThemeName;
+    use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, style::Color};
+    use std::path::PathBuf;
+
+    fn online() -> App {
+        let mut app = App::new(Mode::Online {
+            addresses: vec!["node-1:50051".to_string()],
+        });
+        app.connection_status = ConnectionStatus::Connected;
+        app
+    }
+
+    fn render(app: &App, width: u16, height: u16) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| ui(frame, app)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn row(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_header_uses_theme() {
+        let mut app = online();
+        let buffer = render(&app, 100, 30);
+        assert!(row(&buffer, 1).contains("Status: CONNECTED | RUNNING"));
+        assert_eq!(buffer[(2, 1)].fg, Color::Green);
+
+        app.theme = Theme::new(ThemeName::HighContrast);
+        let buffer = render(&app, 100, 30);
+        assert_eq!(buffer[(2, 1)].fg, Color::Black);
+        assert_eq!(buffer[(2, 1)].bg, Color::LightGreen);
+    }
+
+    #[test]
+    fn test_no_color_header() {
+        let mut app = online();
+        app.theme = Theme::default().without_color();
+        let buffer = render(&app, 100, 30);
+        assert!(row(&buffer, 1).contains("Status: CONNECTED"));
+        for x in 0..buffer.area.width {
+            for y in 0..buffer.area.height {
+                assert_eq!(buffer[(x, y)].fg, Color::Reset);
+                assert_eq!(buffer[(x, y)].bg, Color::Reset);
+            }
+        }
+        // Bold survives so the header still stands out
+        assert!(
+            buffer[(2, 1)]
+                .modifier
+                .contains(ratatui::style::Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn test_offline_header() {
+        let app = App::new(Mode::Offline {
+            path: PathBuf::from("snap"),
+        });
+        let buffer = render(&app, 100, 30);
+        assert!(row(&buffer, 1).contains("Offline: \"snap\" | Status: OFFLINE"));
+        assert_eq!(buffer[(2, 1)].fg, Color::Gray);
+    }
+
+    #[test]
+    fn test_select_screen() {
+        let rect = |width, height| Rect::new(0, 0, width, height);
+        assert_eq!(
+            select_screen(rect(MIN_WIDTH, MIN_HEIGHT)),
+            Screen::Dashboard
+        );
+        assert_eq!(select_screen(rect(200, 60)), Screen::Dashboard);
+        assert_eq!(
+            select_screen(rect(MIN_WIDTH - 1, MIN_HEIGHT)),
+            Screen::TooSmall
+        );
+        assert_eq!(
+            select_screen(rect(MIN_WIDTH, MIN_HEIGHT - 1)),
+            Screen::TooSmall
+        );
+        assert_eq!(select_screen(rect(0, 0)), Screen::TooSmall);
+    }
+
+    #[test]
+    fn test_too_small_notice() {
+        let buffer = render(&online(), MIN_WIDTH - 1, MIN_HEIGHT);
+        let notice = row(&buffer, (MIN_HEIGHT - 1) / 2);
+        assert_eq!(notice.trim(), "window too small (need 60x15)");
+        assert!(!(0..MIN_HEIGHT).any(|y| row(&buffer, y).contains("PraBorrow")));
+
+        // Narrower than the notice itself still draws without panicking
+        let buffer = render(&online(), 10, 3);
+        assert_eq!(row(&buffer, 1), "window too");
+        render(&online(), 1, 1);
+    }
+
+    #[test]
+    fn test_every_tab_fits_minimum_size() {
+        let mut app = online();
+        for (tab, title) in TAB_TITLES.iter().enumerate() {
+            app.tab_index = tab;
+            let buffer = render(&app, MIN_WIDTH, MIN_HEIGHT);
+            assert!(row(&buffer, 1).contains("PraBorrow"), "tab {}", tab);
+            assert!(row(&buffer, 4).contains(title), "tab {}", tab);
+            assert!(
+                row(&buffer, MIN_HEIGHT - 2).contains("Press '?' for help"),
+                "tab {}",
+                tab
+            );
+        }
+    }
+
+    #[test]
+    fn test_help_overlay_is_clipped_to_small_screens() {
+        let mut app = online();
+        app.show_help = true;
+        let buffer = render(&app, MIN_WIDTH, MIN_HEIGHT);
+        assert!(row(&buffer, 0).contains("Key Bindings (Esc to close)"));
+    }
+
+    #[test]
+    fn test_connection_label_shows_last_seen() {
+        let mut node = NodeSample {
+            last_seen_ms: Some(1_714_566_605_000),
+            ..node_sample("node-2:50051", 2, "Follower", 3)
+        };
+        assert_eq!(connection_label(&node), "connected");
+
+        node.missed_polls = 2;
+        assert_eq!(
+            connection_label(&node),
+            "missed 2 polls (last seen 12:30:05Z)"
+        );
+
+        node.connection = ConnectionStatus::Reconnecting(4);
+        assert_eq!(
+            connection_label(&node),
+            "reconnecting (4s) (last seen 12:30:05Z)"
+        );
+
+        node.last_seen_ms = None;
+        assert_eq!(connection_label(&node), "reconnecting (4s)");
+    }
+}
@@ -0,0 +1,449 @@
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use xshell::{cmd, Shell};
+
+/// Committed output of `uniffi-bindgen`.
+pub const PYTHON_OUT: &str = "bindings/python";
+
+const DART_CRATE: &str = "crates/praborrow-dart";
+/// Committed Rust side of the Dart bindings, relative to [`DART_CRATE`].
+const FRB_RUST_OUT: &str = "src/frb_generated.rs";
+/// Dart package the codegen writes into, relative to [`DART_CRATE`].
+const DART_PACKAGE: &str = "dart_lib";
+
+/// Why a generator could not run.
+#[derive(Debug)]
+pub enum GenerateError {
+    /// The tool is not installed.
+    MissingTool(&'static str),
+    Failed(anyhow::Error),
+}
+
+impl From<anyhow::Error> for GenerateError {
+    fn from(e: anyhow::Error) -> Self {
+        GenerateError::Failed(e)
+    }
+}
+
+impl From<xshell::Error> for GenerateError {
+    fn from(e: xshell::Error) -> Self {
+        GenerateError::Failed(e.into())
+    }
+}
+
+impl From<std::io::Error> for GenerateError {
+    fn from(e: std::io::Error) -> Self {
+        GenerateError::Failed(e.into())
+    }
+}
+
+/// Generates the Python module for `praborrow-bindings` into `out_dir`.
+pub fn generate_python(sh: &Shell, out_dir: &Path) -> Result<(), GenerateError> {
+    if cmd!(sh, "uniffi-bindgen --version").quiet().run().is_err() {
+        return Err(GenerateError::MissingTool("uniffi-bindgen"));
+    }
+
+    // Ensure the library is built (needed for proc-macro based generation)
+    println!("  Compiling praborrow-bindings...");
+    cmd!(sh, "cargo build -p praborrow-bindings")
+        .quiet()
+        .run()?;
+    fs::create_dir_all(out_dir)?;
+
+    // Determine library path (heuristics for Windows/Unix)
+    let lib_name = "praborrow_bindings";
+    let lib_path = if cfg!(windows) {
+        format!("target/debug/{}.dll", lib_name)
+    } else if cfg!(target_os = "macos") {
+        format!("target/debug/lib{}.dylib", lib_name)
+    } else {
+        format!("target/debug/lib{}.so", lib_name)
+    };
+    if !sh.path_exists(&lib_path) {
+        return Err(anyhow::anyhow!("Library not found at {}. Build failed?", lib_path).into());
+    }
+
+    // Unformatted, so the output does not depend on whether yapf is installed
+    cmd!(
+        sh,
+        "uniffi-bindgen generate --library {lib_path} --language python --no-format --out-dir {out_dir}"
+    )
+    .run()?;
+    Ok(())
+}
+
+/// Runs `flutter_rust_bridge_codegen` for `praborrow-dart`. With `out_dir` the Dart
+/// package is copied to `out_dir/dart` and generated there, and the Rust output is
+/// copied to `out_dir/rust`.
+///
+/// The codegen only writes Rust output inside the crate, so with `out_dir` it runs in
+/// place and the committed `frb_generated.rs` is restored afterwards. It also formats
+/// Rust as edition 2018; the output is reformatted for the workspace edition so it
+/// passes `cargo fmt --check`.
+pub fn generate_dart(sh: &Shell, out_dir: Option<&Path>) -> Result<(), GenerateError> {
+    if cmd!(sh, "flutter_rust_bridge_codegen --version")
+        .quiet()
+        .run()
+        .is_err()
+    {
+        return Err(GenerateError::MissingTool("flutter_rust_bridge_codegen"));
+    }
+
+    let out_dir = out_dir.map(|dir| sh.current_dir().join(dir));
+    let _guard = sh.push_dir(DART_CRATE);
+    let rust_output = sh.current_dir().join(FRB_RUST_OUT);
+    let Some(dir) = out_dir else {
+        cmd!(sh, "flutter_rust_bridge_codegen generate").run()?;
+        cmd!(sh, "rustfmt --edition 2024 {rust_output}")
+            .quiet()
+            .run()?;
+        return Ok(());
+    };
+
+    let package = dir.join("dart");
+    let dart_output = package.join("lib").join("src").join("rust");
+    fs::create_dir_all(&dart_output)?;
+    fs::create_dir_all(dir.join("rust"))?;
+    let pubspec = Path::new(DART_PACKAGE).join("pubspec.yaml");
+    fs::copy(
+        sh.current_dir().join(&pubspec),
+        package.join("pubspec.yaml"),
+    )?;
+    let committed = fs::read(&rust_output).ok();
+    let result = cmd!(
+        sh,
+        "flutter_rust_bridge_codegen generate --dart-output {dart_output}"
+    )
+    .run()
+    .and_then(|()| {
+        cmd!(sh, "rustfmt --edition 2024 {rust_output}")
+            .quiet()
+            .run()
+    })
+    .map_err(GenerateError::from)
+    .and_then(|()| {
+        fs::copy(&rust_output, dir.join("rust").join("frb_generated.rs"))?;
+        Ok(())
+    });
+    match committed {
+        Some(contents) => fs::write(&rust_output, contents)?,
+        None => fs::remove_file(&rust_output)?,
+    }
+    result
+}
+
+/// How a regenerated file differs from its committed counterpart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDiff {
+    /// Generated but not committed.
+    Missing,
+    /// Line counts ignore moves: a line is added when the committed file has fewer
+    /// copies of it than the regenerated one.
+    Changed { added: usize, removed: usize },
+}
+
+/// Compares every file under `generated` with the same path under `committed`.
+///
+/// Only generated files are considered, so hand-written files living next to the
+/// committed output are not reported.
+pub fn diff_trees(generated: &Path, committed: &Path) -> Result<Vec<(PathBuf, FileDiff)>> {
+    let mut diffs = Vec::new();
+    for relative in list_files(generated)? {
+        let fresh = fs::read(generated.join(&relative))?;
+        let diff = match fs::read(committed.join(&relative)) {
+            Ok(old) if old == fresh => continue,
+            Ok(old) => {
+                let (added, removed) = line_delta(
+                    &String::from_utf8_lossy(&old),
+                    &String::from_utf8_lossy(&fresh),
+                );
+                FileDiff::Changed { added, removed }
+            }
+            Err(_) => FileDiff::Missing,
+        };
+        diffs.push((relative, diff));
+    }
+    Ok(diffs)
+}
+
+/// Files under `root` as sorted relative paths.
+fn list_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut stack = vec![PathBuf::new()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(root.join(&dir)) else {
+            continue;
+        };
+        for entry in entries {
+            let entry = entry?;
+            let relative = dir.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                stack.push(relative);
+            } else {
+                files.push(relative);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Lines added and removed going from `old` to `new`, compared as multisets.
+pub fn line_delta(old: &str, new: &str) -> (usize, usize) {
+    let mut counts: HashMap<&str, isize> = HashMap::new();
+    for line in old.lines() {
+        *counts.entry(line).or_default() -= 1;
+    }
+    for line in new.lines() {
+        *counts.entry(line).or_default() += 1;
+    }
+    counts.values().fold((0, 0), |(added, removed), &n| {
+        if n > 0 {
+            (added + n as usize, removed)
+        } else {
+            (added, removed + n.unsigned_abs())
+        }
+    })
+}
+
+/// Per-file summary of stale output, one line per file.
+pub fn format_report(committed: &Path, diffs: &[(PathBuf, FileDiff)]) -> String {
+    let mut out = String::new();
+    for (relative, diff) in diffs {
+        let path = committed.join(relative);
+        let line = match diff {
+            FileDiff::Missing => format!("   {} (not committed)\n", path.display()),
+            FileDiff::Changed { added, removed } => {
+                format!("   {} (+{} -{})\n", path.display(), added, removed)
+            }
+        };
+        out.push_str(&line);
+    }
+    out
+}
+
+#[derive(Debug)]
+pub enum CheckStatus {
+    UpToDate,
+    Stale(Vec<(PathBuf, FileDiff)>),
+    Skipped(&'static str),
+}
+
+fn check_target(
+    name: &str,
+    committed: &Path,
+    generated: &Path,
+    result: Result<(), GenerateError>,
+    allow_missing_tools: bool,
+) -> Result<CheckStatus> {
+    match result {
+        Ok(()) => {}
+        Err(GenerateError::MissingTool(tool)) if allow_missing_tools => {
+            println!(
+                "{}",
+                format!("   ⏭️  {}: skipped ({} not installed)", name, tool).yellow()
+            );
+            return Ok(CheckStatus::Skipped(tool));
+        }
+        Err(GenerateError::MissingTool(tool)) => {
+            anyhow::bail!(
+                "{} not installed; pass --allow-missing-tools to skip {} bindings",
+                tool,
+                name
+            );
+        }
+        Err(GenerateError::Failed(e)) => {
+            return Err(e.context(format!("{} binding generation failed", name)))
+        }
+    }
+
+    let diffs = diff_trees(generated, committed)?;
+    if diffs.is_empty() {
+        println!("{}", format!("   ✅ {}: up to date", name).green());
+        return Ok(CheckStatus::UpToDate);
+    }
+    println!(
+        "{}",
+        format!("   ❌ {}: {} stale file(s)", name, diffs.len()).red()
+    );
+    print!("{}", format_report(committed, &diffs));
+    Ok(CheckStatus::Stale(diffs))
+}
+
+/// Regenerates every binding into a scratch dir and fails if the committed output
+/// differs.
+pub fn run_check(sh: &Shell, allow_missing_tools: bool) -> Result<()> {
+    println!("{}", "🔗 Checking generated bindings...".cyan().bold());
+
+    let scratch = std::env::temp_dir().join("praborrow-bindings-check");
+    if scratch.exists() {
+        fs::remove_dir_all(&scratch)?;
+    }
+
+    let python_dir = scratch.join("python");
+    let python = check_target(
+        "Python",
+        Path::new(PYTHON_OUT),
+        &python_dir,
+        generate_python(sh, &python_dir),
+        allow_missing_tools,
+    )?;
+
+    // Only the Rust glue is committed; the Dart side belongs to the Flutter package and
+    // needs `dart format` and `build_runner`, which this repository does not run
+    let dart = check_target(
+        "Dart",
+        &Path::new(DART_CRATE).join("src"),
+        &scratch.join("dart").join("rust"),
+        generate_dart(sh, Some(&scratch.join("dart"))),
+        allow_missing_tools,
+    )?;
+
+    fs::remove_dir_all(&scratch).ok();
+
+    let stale = [&python, &dart]
+        .iter()
+        .filter(|status| matches!(status, CheckStatus::Stale(_)))
+        .count();
+    if stale > 0 {
+        anyhow::bail!(
+            "Bindings are stale; run `cargo xtask generate-bindings` and commit the output"
+        );
+    }
+    println!("{}", "✅ Bindings up to date".green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tree(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_diff_trees() {
+        let generated = tree(&[
+            ("praborrow.py", "a\nb\nc\n"),
+            ("api/cluster.dart", "x\n"),
+            ("same.txt", "unchanged\n"),
+        ]);
+        let committed = tree(&[
+            ("praborrow.py", "a\nc\nd\ne\n"),
+            ("same.txt", "unchanged\n"),
+            ("README.md", "hand-written\n"),
+        ]);
+
+        let diffs = diff_trees(generated.path(), committed.path()).unwrap();
+        assert_eq!(
+            diffs,
+            vec![
+                (PathBuf::from("api/cluster.dart"), FileDiff::Missing),
+                (
+                    PathBuf::from("praborrow.py"),
+                    FileDiff::Changed {
+                        added: 1,
+                        removed: 2
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identical_trees_have_no_diffs() {
+        let files = [("frb_generated.rs", "fn main() {}\n")];
+        let generated = tree(&files);
+        let committed = tree(&files);
+        assert!(diff_trees(generated.path(), committed.path())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_line_delta_ignores_moves() {
+        assert_eq!(line_delta("a\nb\n", "b\na\n"), (0, 0));
+        assert_eq!(line_delta("a\na\n", "a\n"), (0, 1));
+        assert_eq!(line_delta("", "a\nb\n"), (2, 0));
+    }
+
+    #[test]
+    fn test_format_report() {
+        let diffs = vec![
+            (PathBuf::from("praborrow.py"), FileDiff::Missing),
+            (
+                PathBuf::from("frb_generated.rs"),
+                FileDiff::Changed {
+                    added: 3,
+                    removed: 1,
+                },
+            ),
+        ];
+        let committed = Path::new("bindings");
+        assert_eq!(
+            format_report(committed, &diffs),
+            format!(
+                "   {} (not committed)\n   {} (+3 -1)\n",
+                committed.join("praborrow.py").display(),
+                committed.join("frb_generated.rs").display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_missing_tool_is_skipped_only_when_allowed() {
+        let empty = tree(&[]);
+        let status = check_target(
+            "Python",
+            empty.path(),
+            empty.path(),
+            Err(GenerateError::MissingTool("uniffi-bindgen")),
+            true,
+        )
+        .unwrap();
+        assert!(matches!(status, CheckStatus::Skipped("uniffi-bindgen")));
+
+        let err = check_target(
+            "Python",
+            empty.path(),
+            empty.path(),
+            Err(GenerateError::MissingTool("uniffi-bindgen")),
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--allow-missing-tools"));
+    }
+
+    #[test]
+    fn test_generation_failure_is_never_skipped() {
+        let empty = tree(&[]);
+        let result = check_target(
+            "Dart",
+            empty.path(),
+            empty.path(),
+            Err(GenerateError::Failed(anyhow::anyhow!("codegen crashed"))),
+            true,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_stale_output_is_reported() {
+        let generated = tree(&[("praborrow.py", "new\n")]);
+        let committed = tree(&[("praborrow.py", "old\n")]);
+        let status =
+            check_target("Python", committed.path(), generated.path(), Ok(()), false).unwrap();
+        assert!(matches!(status, CheckStatus::Stale(diffs) if diffs.len() == 1));
+    }
+}
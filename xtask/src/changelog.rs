@@ -0,0 +1,510 @@
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use xshell::{cmd, Shell};
+
+const CHANGELOG: &str = "CHANGELOG.md";
+const TITLE: &str = "# Changelog";
+const UNRELEASED: &str = "Unreleased";
+
+/// Bucket for changes outside `crates/`.
+const WORKSPACE: &str = "workspace";
+
+/// Field and record separators for the `git log` format; they never occur in messages.
+const FIELD: char = '\u{1f}';
+const RECORD: char = '\u{1e}';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    pub hash: String,
+    pub subject: String,
+    pub body: String,
+    /// Paths relative to the repository root, submodule paths included.
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Breaking,
+    Feat,
+    Fix,
+    Perf,
+    Other,
+}
+
+impl Section {
+    pub fn title(self) -> &'static str {
+        match self {
+            Section::Breaking => "Breaking Changes",
+            Section::Feat => "Features",
+            Section::Fix => "Bug Fixes",
+            Section::Perf => "Performance",
+            Section::Other => "Other",
+        }
+    }
+}
+
+/// Changes grouped by crate, then section; entries keep `git log` order.
+pub type Groups = BTreeMap<String, BTreeMap<Section, Vec<String>>>;
+
+/// Classifies a subject like `feat(lease)!: add renewal` and returns its entry text.
+///
+/// Anything that is not `type(scope)?!?: description` lands in `Other` verbatim, as do
+/// conventional types other than feat, fix and perf.
+pub fn classify(subject: &str, body: &str) -> (Section, String) {
+    let other = (Section::Other, subject.trim().to_string());
+    let Some((head, description)) = subject.split_once(": ") else {
+        return other;
+    };
+    let (head, bang) = match head.strip_suffix('!') {
+        Some(head) => (head, true),
+        None => (head, false),
+    };
+    let kind = match head.split_once('(') {
+        Some((kind, scope)) if scope.ends_with(')') => kind,
+        Some(_) => return other,
+        None => head,
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return other;
+    }
+
+    let description = description.trim().to_string();
+    if bang || body.contains("BREAKING CHANGE") {
+        return (Section::Breaking, description);
+    }
+    match kind.to_ascii_lowercase().as_str() {
+        "feat" => (Section::Feat, description),
+        "fix" => (Section::Fix, description),
+        "perf" => (Section::Perf, description),
+        _ => other,
+    }
+}
+
+/// Crate a path belongs to: `crates/<name>/...` maps to `<name>`, the rest to the workspace.
+pub fn crate_for_path(path: &str) -> &str {
+    match path.strip_prefix("crates/") {
+        Some(rest) => rest
+            .split('/')
+            .next()
+            .filter(|n| !n.is_empty())
+            .unwrap_or(WORKSPACE),
+        None => WORKSPACE,
+    }
+}
+
+/// Groups commits by every crate they touch. Submodule pointer bumps are ignored, since
+/// the submodule's own commits are collected separately.
+pub fn group(commits: &[Commit], submodules: &[String]) -> Groups {
+    let mut groups = Groups::new();
+    for commit in commits {
+        let (section, entry) = classify(&commit.subject, &commit.body);
+        let short = &commit.hash[..commit.hash.len().min(7)];
+        let entry = format!("{} ({})", entry, short);
+
+        let mut crates: Vec<&str> = commit
+            .files
+            .iter()
+            .filter(|file| !submodules.contains(file))
+            .map(|file| crate_for_path(file))
+            .collect();
+        crates.sort();
+        crates.dedup();
+        for name in crates {
+            groups
+                .entry(name.to_string())
+                .or_default()
+                .entry(section)
+                .or_default()
+                .push(entry.clone());
+        }
+    }
+    groups
+}
+
+/// Renders one crate's release section.
+pub fn render_section(version: &str, sections: &BTreeMap<Section, Vec<String>>) -> String {
+    let mut out = format!("## {}\n", version_title(version));
+    for (section, entries) in sections {
+        out.push_str(&format!("\n### {}\n\n", section.title()));
+        for entry in entries {
+            out.push_str(&format!("- {}\n", entry));
+        }
+    }
+    out
+}
+
+/// Renders the root aggregate: one subsection per crate.
+pub fn render_aggregate(version: &str, groups: &Groups) -> String {
+    let mut out = format!("## {}\n", version_title(version));
+    for (name, sections) in groups {
+        out.push_str(&format!("\n### {}\n", name));
+        for (section, entries) in sections {
+            out.push_str(&format!("\n#### {}\n\n", section.title()));
+            for entry in entries {
+                out.push_str(&format!("- {}\n", entry));
+            }
+        }
+    }
+    out
+}
+
+fn version_title(version: &str) -> String {
+    if version == UNRELEASED {
+        UNRELEASED.to_string()
+    } else {
+        format!("[{}]", version)
+    }
+}
+
+/// Inserts `section` as the newest entry of a changelog, replacing a previous
+/// `Unreleased` section so reruns don't pile up.
+pub fn insert_section(existing: &str, section: &str) -> String {
+    let body = existing
+        .strip_prefix(TITLE)
+        .unwrap_or(existing)
+        .trim_start();
+    let body = match body.strip_prefix(&format!("## {}\n", UNRELEASED)) {
+        Some(rest) => match rest.find("\n## ") {
+            Some(next) => &rest[next + 1..],
+            None => "",
+        },
+        None => body,
+    };
+    let mut out = format!("{}\n\n{}", TITLE, section);
+    if !body.is_empty() {
+        out.push('\n');
+        out.push_str(body);
+    }
+    out
+}
+
+/// Parses `git log` output produced with [`LOG_FORMAT`].
+pub fn parse_log(output: &str, prefix: &str) -> Vec<Commit> {
+    output
+        .split(RECORD)
+        .filter(|record| !record.trim().is_empty())
+        .filter_map(|record| {
+            let mut fields = record.splitn(4, FIELD);
+            let hash = fields.next()?.trim().to_string();
+            let subject = fields.next()?.to_string();
+            let body = fields.next()?.to_string();
+            let files = fields
+                .next()
+                .unwrap_or("")
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|file| format!("{}{}", prefix, file))
+                .collect();
+            Some(Commit {
+                hash,
+                subject,
+                body,
+                files,
+            })
+        })
+        .collect()
+}
+
+/// `%x1e` starts a record; hash, subject and body are split by `%x1f`, and
+/// `--name-only` appends the touched files after the last separator.
+pub const LOG_FORMAT: &str = "--format=%x1e%H%x1f%s%x1f%b%x1f";
+
+pub fn submodule_paths(sh: &Shell) -> Vec<String> {
+    sh.read_file(".gitmodules")
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("path = "))
+                .map(|path| path.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Collects commits since `since` from the root repository and every checked out
+/// submodule. Submodules have their own tags, so they are cut off by the tag's date.
+fn collect_commits(sh: &Shell, since: Option<&str>, submodules: &[String]) -> Result<Vec<Commit>> {
+    let format = LOG_FORMAT;
+    let mut commits = match since {
+        Some(tag) => {
+            let range = format!("{}..HEAD", tag);
+            parse_log(
+                &cmd!(sh, "git log --name-only {format} {range}").read()?,
+                "",
+            )
+        }
+        None => parse_log(&cmd!(sh, "git log --name-only {format}").read()?, ""),
+    };
+
+    let since_date = match since {
+        Some(tag) => Some(cmd!(sh, "git log -1 --format=%cI {tag}").read()?),
+        None => None,
+    };
+    for path in submodules {
+        if !sh.path_exists(format!("{}/.git", path)) {
+            continue;
+        }
+        let _guard = sh.push_dir(path);
+        let prefix = format!("{}/", path);
+        let output = match &since_date {
+            Some(date) => {
+                let since_arg = format!("--since={}", date);
+                cmd!(sh, "git log --name-only {format} {since_arg}").read()?
+            }
+            None => cmd!(sh, "git log --name-only {format}").read()?,
+        };
+        commits.extend(parse_log(&output, &prefix));
+    }
+    Ok(commits)
+}
+
+fn latest_tag(sh: &Shell) -> Option<String> {
+    cmd!(sh, "git describe --tags --abbrev=0")
+        .quiet()
+        .ignore_stderr()
+        .read()
+        .ok()
+}
+
+/// Writes per-crate and root changelogs. `version` of `None` writes an `Unreleased`
+/// section; `Release` passes the new version.
+pub fn run_changelog(sh: &Shell, since: Option<String>, version: Option<&str>) -> Result<()> {
+    println!("{}", "📝 Generating changelog...".cyan().bold());
+
+    let since = since.or_else(|| latest_tag(sh));
+    match &since {
+        Some(tag) => println!("   Changes since {}", tag.cyan()),
+        None => println!("   No tag found; using full history"),
+    }
+
+    let submodules = submodule_paths(sh);
+    let commits = collect_commits(sh, since.as_deref(), &submodules)?;
+    let groups = group(&commits, &submodules);
+    if groups.is_empty() {
+        println!("{}", "⚠️  No changes found".yellow());
+        return Ok(());
+    }
+
+    let version = version.unwrap_or(UNRELEASED);
+    for (name, sections) in &groups {
+        if name == WORKSPACE {
+            continue;
+        }
+        let dir = Path::new("crates").join(name);
+        if !dir.exists() {
+            continue;
+        }
+        let path = dir.join(CHANGELOG);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        fs::write(
+            &path,
+            insert_section(&existing, &render_section(version, sections)),
+        )?;
+        println!("   ✅ {}", path.display());
+    }
+
+    let existing = fs::read_to_string(CHANGELOG).unwrap_or_default();
+    fs::write(
+        CHANGELOG,
+        insert_section(&existing, &render_aggregate(version, &groups)),
+    )?;
+    println!("   ✅ {}", CHANGELOG);
+
+    println!(
+        "{}",
+        format!("✅ Changelog updated ({} commits)", commits.len()).green()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, subject: &str, body: &str, files: &[&str]) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+            files: files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            ("feat(lease): add renewal", "", Section::Feat, "add renewal"),
+            ("fix: off-by-one", "", Section::Fix, "off-by-one"),
+            (
+                "perf(core): faster deref",
+                "",
+                Section::Perf,
+                "faster deref",
+            ),
+            ("Feat: capitalised", "", Section::Feat, "capitalised"),
+            ("feat(core)!: drop Hire", "", Section::Breaking, "drop Hire"),
+            (
+                "refactor: tidy",
+                "BREAKING CHANGE: gone",
+                Section::Breaking,
+                "tidy",
+            ),
+            ("docs: typo", "", Section::Other, "docs: typo"),
+            ("Update README", "", Section::Other, "Update README"),
+            (
+                "feat(lease: broken",
+                "",
+                Section::Other,
+                "feat(lease: broken",
+            ),
+            ("wip stuff: more", "", Section::Other, "wip stuff: more"),
+        ];
+        for (subject, body, section, entry) in cases {
+            assert_eq!(
+                classify(subject, body),
+                (section, entry.to_string()),
+                "{}",
+                subject
+            );
+        }
+    }
+
+    #[test]
+    fn test_crate_for_path() {
+        assert_eq!(
+            crate_for_path("crates/praborrow-core/src/lib.rs"),
+            "praborrow-core"
+        );
+        assert_eq!(crate_for_path("crates/prb-cli"), "prb-cli");
+        assert_eq!(crate_for_path("xtask/src/main.rs"), WORKSPACE);
+        assert_eq!(crate_for_path("crates/"), WORKSPACE);
+    }
+
+    #[test]
+    fn test_group_by_touched_crates() {
+        let submodules = vec!["crates/praborrow-core".to_string()];
+        let commits = vec![
+            commit(
+                "aaaaaaaaaa",
+                "feat(lease): add renewal",
+                "",
+                &[
+                    "crates/praborrow-lease/src/lib.rs",
+                    "crates/praborrow/src/lib.rs",
+                ],
+            ),
+            commit(
+                "bbbbbbbbbb",
+                "fix: pin versions",
+                "",
+                &["xtask/src/main.rs"],
+            ),
+            // Pointer bump only; the submodule's own commits are collected separately
+            commit(
+                "cccccccccc",
+                "chore: bump core",
+                "",
+                &["crates/praborrow-core"],
+            ),
+            commit(
+                "dddddddddd",
+                "fix(core): reclaim",
+                "",
+                &["crates/praborrow-core/src/lib.rs"],
+            ),
+        ];
+
+        let groups = group(&commits, &submodules);
+        assert_eq!(
+            groups.keys().collect::<Vec<_>>(),
+            vec!["praborrow", "praborrow-core", "praborrow-lease", WORKSPACE]
+        );
+        assert_eq!(
+            groups["praborrow-lease"][&Section::Feat],
+            vec!["add renewal (aaaaaaa)"]
+        );
+        assert_eq!(
+            groups["praborrow"][&Section::Feat],
+            vec!["add renewal (aaaaaaa)"]
+        );
+        assert_eq!(
+            groups["praborrow-core"][&Section::Fix],
+            vec!["reclaim (ddddddd)"]
+        );
+        assert_eq!(
+            groups[WORKSPACE][&Section::Fix],
+            vec!["pin versions (bbbbbbb)"]
+        );
+    }
+
+    #[test]
+    fn test_render_section() {
+        let mut sections = BTreeMap::new();
+        sections.insert(Section::Other, vec!["Update README (1234567)".to_string()]);
+        sections.insert(Section::Feat, vec!["add renewal (aaaaaaa)".to_string()]);
+        assert_eq!(
+            render_section("1.2.3", &sections),
+            "## [1.2.3]\n\n### Features\n\n- add renewal (aaaaaaa)\n\n### Other\n\n- Update README (1234567)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_aggregate() {
+        let mut groups = Groups::new();
+        groups
+            .entry("praborrow-core".to_string())
+            .or_default()
+            .insert(Section::Fix, vec!["reclaim (ddddddd)".to_string()]);
+        assert_eq!(
+            render_aggregate(UNRELEASED, &groups),
+            "## Unreleased\n\n### praborrow-core\n\n#### Bug Fixes\n\n- reclaim (ddddddd)\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_section() {
+        let section = "## [1.2.3]\n\n### Features\n\n- new\n";
+        assert_eq!(
+            insert_section("", section),
+            "# Changelog\n\n## [1.2.3]\n\n### Features\n\n- new\n"
+        );
+
+        let existing = "# Changelog\n\n## [1.2.2]\n\n### Bug Fixes\n\n- old\n";
+        assert_eq!(
+            insert_section(existing, section),
+            "# Changelog\n\n## [1.2.3]\n\n### Features\n\n- new\n\n## [1.2.2]\n\n### Bug Fixes\n\n- old\n"
+        );
+
+        // A rerun replaces the previous Unreleased section
+        let existing = "# Changelog\n\n## Unreleased\n\n- stale\n\n## [1.2.2]\n\n- old\n";
+        assert_eq!(
+            insert_section(existing, "## Unreleased\n\n- fresh\n"),
+            "# Changelog\n\n## Unreleased\n\n- fresh\n\n## [1.2.2]\n\n- old\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let output = "\u{1e}abc\u{1f}feat: one\u{1f}body line\u{1f}\n\nsrc/lib.rs\nCargo.toml\n\u{1e}def\u{1f}fix: two\u{1f}\u{1f}\n";
+        let commits = parse_log(output, "crates/praborrow-core/");
+        assert_eq!(
+            commits,
+            vec![
+                commit(
+                    "abc",
+                    "feat: one",
+                    "body line",
+                    &[
+                        "crates/praborrow-core/src/lib.rs",
+                        "crates/praborrow-core/Cargo.toml"
+                    ],
+                ),
+                commit("def", "fix: two", "", &[]),
+            ]
+        );
+    }
+}
@@ -36,8 +36,27 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Check that published crates are indexed and the facade builds from crates.io
+    #[command(name = "verify-publish")]
+    VerifyPublish {
+        /// Version to verify (defaults to the workspace version)
+        #[arg(long)]
+        version: Option<String>,
+        /// Seconds to wait for the crates.io index
+        #[arg(long, default_value_t = verify::DEFAULT_TIMEOUT_SECS)]
+        timeout: u64,
+    },
+    /// Update CHANGELOG.md files from conventional commits, grouped by crate
+    Changelog {
+        /// Tag to start from (defaults to the latest tag)
+        #[arg(long)]
+        since: Option<String>,
+    },
     /// Run pre-flight checks (audit, outdated, clean git)
     PreFlight,
+    /// Verify internal praborrow-* dependency versions match the workspace version
+    #[command(name = "check-versions")]
+    CheckVersions,
     /// Bump version across all workspace crates
     BumpVersion {
         /// Version bump type
@@ -56,11 +75,22 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
-    /// Run CI checks (fmt, clippy, test, deny)
-    CI,
+    /// Run CI checks (fmt, clippy, test, deny, bindings)
+    CI {
+        /// Report a missing binding generator as skipped instead of failing
+        #[arg(long)]
+        allow_missing_tools: bool,
+    },
     /// Generate bindings for Python (UniFFI) and Dart (FRB)
     #[command(name = "generate-bindings")]
-    GenerateBindings,
+    GenerateBindings {
+        /// Regenerate into a temp dir and fail if the committed bindings are stale
+        #[arg(long)]
+        check: bool,
+        /// With --check, report a missing generator as skipped instead of failing
+        #[arg(long, requires = "check")]
+        allow_missing_tools: bool,
+    },
     /// Publish Python bindings to PyPI
     #[command(name = "publish-pypi")]
     PublishPyPI,
@@ -101,7 +131,12 @@ enum BumpType {
     Major,
 }
 
+pub mod bindings;
+pub mod changelog;
 pub mod publish;
+pub mod rollback;
+pub mod verify;
+pub mod versions;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -120,6 +155,19 @@ fn main() -> Result<()> {
         }
 
         Commands::PreFlight => run_preflight(&sh)?,
+        Commands::CheckVersions => versions::run_check_versions()?,
+        Commands::Changelog { since } => changelog::run_changelog(&sh, since, None)?,
+        Commands::VerifyPublish { version, timeout } => {
+            let version = match version {
+                Some(version) => version,
+                None => verify::workspace_version()?,
+            };
+            verify::run_verify_publish(
+                &publish::publishable_crates()?,
+                &version,
+                std::time::Duration::from_secs(timeout),
+            )?
+        }
         Commands::GitSync { message, push } => run_git_sync(&sh, &message, push)?,
         Commands::Publish { dry_run } => publish::run_publish_parallel(&sh, dry_run)?,
         Commands::BumpVersion { bump_type } => run_bump_version(&sh, bump_type)?,
@@ -128,8 +176,13 @@ fn main() -> Result<()> {
             skip_publish,
             dry_run,
         } => run_release(&sh, bump_type, skip_publish, dry_run)?,
-        Commands::CI => run_ci(&sh)?,
-        Commands::GenerateBindings => run_generate_bindings(&sh)?,
+        Commands::CI {
+            allow_missing_tools,
+        } => run_ci(&sh, allow_missing_tools)?,
+        Commands::GenerateBindings {
+            check,
+            allow_missing_tools,
+        } => run_generate_bindings(&sh, check, allow_missing_tools)?,
         Commands::PublishPyPI => run_publish_pypi(&sh)?,
         Commands::PublishNpm => run_publish_npm(&sh)?,
         Commands::PublishJsr => run_publish_jsr(&sh)?,
@@ -145,7 +198,7 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_ci(sh: &Shell) -> Result<()> {
+fn run_ci(sh: &Shell, allow_missing_tools: bool) -> Result<()> {
     println!("{}", "🔍 Running CI checks...".cyan().bold());
 
     // 1. Format Check
@@ -184,6 +237,13 @@ fn run_ci(sh: &Shell) -> Result<()> {
         println!("{}", "⚠️  cargo-deny skipped (not installed)".yellow());
     }
 
+    // 5. Generated bindings
+    println!("{}", "🔗 Checking generated bindings...".dimmed());
+    if let Err(e) = bindings::run_check(sh, allow_missing_tools) {
+        println!("{}", "❌ Binding check failed".red());
+        return Err(e);
+    }
+
     println!("\n{}", "🎉 All CI checks passed!".green().bold());
     Ok(())
 }
@@ -262,6 +322,22 @@ fn run_git_sync(sh: &Shell, msg: &str, push: bool) -> Result<()> {
     Ok(())
 }
 
+/// Pushes the current branch of every submodule, then the root.
+fn push_release(sh: &Shell) -> Result<()> {
+    for sub in changelog::submodule_paths(sh) {
+        if !sh.path_exists(format!("{}/.git", sub)) {
+            continue;
+        }
+        let _guard = sh.push_dir(&sub);
+        let current_branch = cmd!(sh, "git branch --show-current").read()?;
+        cmd!(sh, "git push origin {current_branch}").run()?;
+    }
+    let current_branch = cmd!(sh, "git branch --show-current").read()?;
+    cmd!(sh, "git push origin {current_branch}").run()?;
+    println!("{}", "   ✅ Pushed".green());
+    Ok(())
+}
+
 fn run_preflight(sh: &Shell) -> Result<()> {
     println!("{}", "🛫 Running Pre-Flight Checks...".cyan().bold());
 
@@ -269,7 +345,10 @@ fn run_preflight(sh: &Shell) -> Result<()> {
     ensure_clean_git(sh)?;
     println!("{}", "✅ Git workspace is clean".green());
 
-    // 2. Build & Test
+    // 2. Internal version consistency
+    versions::run_check_versions()?;
+
+    // 3. Build & Test
     println!("{}", "🏗️ Building workspace...".dimmed());
     cmd!(sh, "cargo build --workspace").run()?;
     println!("{}", "✅ Build successful".green());
@@ -278,7 +357,7 @@ fn run_preflight(sh: &Shell) -> Result<()> {
     cmd!(sh, "cargo test --workspace").run()?;
     println!("{}", "✅ Tests passed".green());
 
-    // 3. Audit (if installed)
+    // 4. Audit (if installed)
     if cmd!(sh, "cargo audit --version").quiet().run().is_ok() {
         println!("{}", "🔒 Running security audit...".dimmed());
         cmd!(sh, "cargo audit").run()?;
@@ -414,93 +493,121 @@ fn run_release(
         println!("   No crates will be published.\n");
     }
 
-    // Step 1: Bump version
-    println!("\n{}", "Step 1/5: Bumping version...".cyan().bold());
-    if dry_run {
-        println!("   [Dry Run] Would bump version ({:?})", bump_type);
-    } else {
-        match run_bump_version(sh, bump_type) {
-            Ok(_) => {}
-            Err(e) => {
-                println!(
-                    "{}",
-                    "❌ Version bump failed. State is clean (or unchanged).".red()
-                );
-                return Err(e);
-            }
-        }
-    }
-
-    // Get new version (mock for dry run if needed, or read actual if bumped)
-    let new_version = if dry_run {
-        "0.0.0-dryrun".to_string()
+    // Everything the release may touch, so any failure below can be undone exactly
+    let plan = if dry_run {
+        None
     } else {
-        let cargo_toml = fs::read_to_string("Cargo.toml")?;
-        extract_workspace_version(&cargo_toml).unwrap_or_else(|| "unknown".to_string())
+        ensure_clean_git(sh)?;
+        let root = std::path::Path::new(".");
+        Some(rollback::RollbackPlan::capture(
+            root,
+            rollback::release_files(root)?,
+        )?)
     };
 
-    // Wrap subsequent steps in a closure or block to handle rollback
+    let mut new_version = "0.0.0-dryrun".to_string();
     let result = (|| -> Result<()> {
+        // Step 1: Bump version
+        println!("\n{}", "Step 1/6: Bumping version...".cyan().bold());
+        if dry_run {
+            println!("   [Dry Run] Would bump version ({:?})", bump_type);
+        } else {
+            run_bump_version(sh, bump_type)?;
+            let cargo_toml = fs::read_to_string("Cargo.toml")?;
+            new_version =
+                extract_workspace_version(&cargo_toml).unwrap_or_else(|| "unknown".to_string());
+        }
+
+        // A half-bumped workspace would fail midway through the publish layers
+        if !dry_run {
+            versions::run_check_versions()?;
+        }
+
         // Step 2: Build
-        println!("\n{}", "Step 2/5: Building workspace...".cyan().bold());
+        println!("\n{}", "Step 2/6: Building workspace...".cyan().bold());
         println!("$ cargo build --workspace --exclude xtask");
         cmd!(sh, "cargo build --workspace --exclude xtask").run()?;
         println!("{}", "   ✅ Build successful".green());
 
         // Step 3: Test
-        println!("\nStep 3/5: Running tests...");
+        println!("\nStep 3/6: Running tests...");
         println!("$ cargo test --workspace --exclude xtask");
         cmd!(sh, "cargo test --workspace --exclude xtask").run()?;
         println!("{}", "   ✅ All tests passed".green());
+
+        // Changes since the previous release tag, under the new version's header
+        if dry_run {
+            println!("   [Dry Run] Would update CHANGELOG.md files");
+        } else {
+            changelog::run_changelog(sh, None, Some(&new_version))?;
+        }
+
+        // Step 4: Commit locally; nothing is pushed until publishing succeeded
+        println!("\n{}", "Step 4/6: Committing changes...".cyan().bold());
+        let commit_msg = format!("release: v{}", new_version);
+        if dry_run {
+            println!("   [Dry Run] Would commit with message: {:?}", commit_msg);
+        } else {
+            run_git_sync(sh, &commit_msg, false)?;
+        }
+
+        // Step 5: Publish (optional)
+        if skip_publish {
+            println!(
+                "\n{}",
+                "Step 5/6: Skipping publish (--skip-publish)".yellow()
+            );
+        } else {
+            println!("\n{}", "Step 5/6: Publishing to crates.io...".cyan().bold());
+            if !dry_run {
+                let confirmed = dialoguer::Confirm::new()
+                    .with_prompt("Ready to publish to crates.io?")
+                    .default(false)
+                    .interact()?;
+                if !confirmed {
+                    anyhow::bail!("Publish aborted");
+                }
+            }
+            publish::run_publish_parallel(sh, dry_run)?;
+        }
         Ok(())
     })();
 
     if let Err(e) = result {
-        println!("{}", "❌ Build or Test failed.".red());
-        if !dry_run {
-            println!("{}", "   Reverting version bump...".yellow());
-            cmd!(sh, "git checkout Cargo.toml crates/praborrow/Cargo.toml").run()?;
-            // Also need to revert other crates if bumped...
-            // Ideally we'd valid 'git restore .' but that's risky.
-            // Rely on user to check git status.
-            println!(
-                "{}",
-                "⚠️  Please check git status and revert manual changes if needed.".yellow()
-            );
+        println!("{}", format!("❌ Release failed: {:#}", e).red());
+        match (&plan, e.downcast_ref::<publish::PartialPublish>()) {
+            (Some(_), Some(partial)) => {
+                // Dropping the commit would leave published versions the tree no longer declares
+                println!(
+                    "{}",
+                    format!(
+                        "⚠️  Not rolling back: {} already on crates.io. The release commit is kept \
+                         locally; fix the failure and publish the rest, or yank them.",
+                        partial.published.join(", ")
+                    )
+                    .yellow()
+                );
+            }
+            (Some(plan), None) => {
+                // The release error is the one to report; a failed rollback only adds to it
+                if let Err(rollback_err) = roll_back_release(plan) {
+                    println!(
+                        "{}",
+                        format!("❌ Rollback failed: {:#}", rollback_err).red()
+                    );
+                }
+            }
+            (None, _) => {}
         }
         return Err(e);
     }
 
-    // Step 4: Commit and push
-    println!("\n{}", "Step 4/5: Committing changes...".cyan().bold());
-    let commit_msg = format!("release: v{}", new_version);
-
+    // Step 6: Push
+    println!("\n{}", "Step 6/6: Pushing...".cyan().bold());
     if dry_run {
-        println!("   [Dry Run] Would commit with message: {:?}", commit_msg);
         println!("   [Dry Run] Would push to origin");
     } else {
-        run_git_sync(sh, &commit_msg, true)?;
-    }
-
-    // Step 5: Publish (optional)
-    if skip_publish {
-        println!(
-            "\n{}",
-            "Step 5/5: Skipping publish (--skip-publish)".yellow()
-        );
-    } else {
-        println!("\n{}", "Step 5/5: Publishing to crates.io...".cyan().bold());
-        if !dry_run {
-            let confirmed = dialoguer::Confirm::new()
-                .with_prompt("Ready to publish to crates.io?")
-                .default(false)
-                .interact()?;
-            if !confirmed {
-                println!("{}", "Aborted".yellow());
-                return Ok(());
-            }
-        }
-        publish::run_publish_parallel(sh, dry_run)?;
+        push_release(sh)?;
     }
 
     println!("\n{}", "═".repeat(50).dimmed());
@@ -526,6 +633,32 @@ fn run_release(
     Ok(())
 }
 
+/// Undoes a failed release that published nothing, reporting each step.
+fn roll_back_release(plan: &rollback::RollbackPlan) -> Result<()> {
+    println!("{}", "   Rolling back...".yellow());
+    let report = plan.rollback()?;
+    for (repo, head) in &report.reset {
+        let repo = if repo.as_os_str().is_empty() {
+            "root".to_string()
+        } else {
+            repo.display().to_string()
+        };
+        println!("   ↩️  {} reset to {}", repo, &head[..head.len().min(7)]);
+    }
+    for path in &report.restored {
+        println!("   ↩️  restored {}", path.display());
+    }
+    for path in &report.removed {
+        println!("   ↩️  removed {}", path.display());
+    }
+    if report.is_empty() {
+        println!("   Nothing to roll back.");
+    }
+    plan.verify_clean()?;
+    println!("{}", "   ✅ Working tree restored".green());
+    Ok(())
+}
+
 fn ensure_clean_git(sh: &Shell) -> Result<()> {
     let status = cmd!(sh, "git status --porcelain").read()?;
     if !status.is_empty() {
@@ -534,53 +667,37 @@ fn ensure_clean_git(sh: &Shell) -> Result<()> {
     Ok(())
 }
 
-fn run_generate_bindings(sh: &Shell) -> Result<()> {
+fn run_generate_bindings(sh: &Shell, check: bool, allow_missing_tools: bool) -> Result<()> {
+    if check {
+        return bindings::run_check(sh, allow_missing_tools);
+    }
+
     println!("{}", "🔗 Generating bindings...".cyan().bold());
 
     // 1. Python (UniFFI)
     println!("{}", "\n🐍 Generating Python bindings...".dimmed());
-    if cmd!(sh, "uniffi-bindgen --version").quiet().run().is_ok() {
-        // Ensure the library is built (needed for proc-macro based generation)
-        println!("  Compiling praborrow-bindings...");
-        cmd!(sh, "cargo build -p praborrow-bindings").quiet().run()?;
-
-        let out_dir = "bindings/python";
-        fs::create_dir_all(out_dir)?;
-        
-        // Determine library path (heuristics for Windows/Unix)
-        let lib_name = "praborrow_bindings";
-        let lib_path = if cfg!(windows) {
-            format!("target/debug/{}.dll", lib_name)
-        } else if cfg!(target_os = "macos") {
-            format!("target/debug/lib{}.dylib", lib_name)
-        } else {
-            format!("target/debug/lib{}.so", lib_name)
-        };
-
-        if !sh.path_exists(&lib_path) {
-             anyhow::bail!("Library not found at {}. Build failed?", lib_path);
+    match bindings::generate_python(sh, std::path::Path::new(bindings::PYTHON_OUT)) {
+        Ok(()) => println!("{}", "✅ Python bindings generated in bindings/python".green()),
+        Err(bindings::GenerateError::MissingTool(_)) => {
+            println!("{}", "⚠️  uniffi-bindgen not found. Install with: cargo install uniffi-bindgen".yellow());
         }
-
-        if let Err(e) = cmd!(sh, "uniffi-bindgen generate {lib_path} --language python --out-dir {out_dir}").run() {
-             println!("{}", "❌ Python binding generation failed".red());
-             return Err(e.into());
+        Err(bindings::GenerateError::Failed(e)) => {
+            println!("{}", "❌ Python binding generation failed".red());
+            return Err(e);
         }
-        println!("{}", "✅ Python bindings generated in bindings/python".green());
-    } else {
-        println!("{}", "⚠️  uniffi-bindgen not found. Install with: cargo install uniffi-bindgen".yellow());
     }
 
     // 2. Dart (Flutter Rust Bridge)
     println!("{}", "\n🎯 Generating Dart bindings...".dimmed());
-    if cmd!(sh, "flutter_rust_bridge_codegen --version").quiet().run().is_ok() {
-        let _guard = sh.push_dir("crates/praborrow-dart");
-        if let Err(e) = cmd!(sh, "flutter_rust_bridge_codegen generate").run() {
+    match bindings::generate_dart(sh, None) {
+        Ok(()) => println!("{}", "✅ Dart bindings generated".green()),
+        Err(bindings::GenerateError::MissingTool(_)) => {
+            println!("{}", "⚠️  flutter_rust_bridge_codegen not found. Install with: cargo install flutter_rust_bridge_codegen".yellow());
+        }
+        Err(bindings::GenerateError::Failed(e)) => {
             println!("{}", "❌ Dart binding generation failed".red());
-            return Err(e.into());
+            return Err(e);
         }
-        println!("{}", "✅ Dart bindings generated".green());
-    } else {
-        println!("{}", "⚠️  flutter_rust_bridge_codegen not found. Install with: cargo install flutter_rust_bridge_codegen".yellow());
     }
 
     println!("\n{}", "🎉 Binding generation task complete.".green().bold());
@@ -1,15 +1,60 @@
 use anyhow::{Context, Result};
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 use xshell::{cmd, Shell};
 
+use crate::verify;
+
+/// Repository-level xtask settings.
+const CONFIG_FILE: &str = "xtask.toml";
+
+/// Pause between layers so the next one resolves the crates just published.
+const INDEX_PROPAGATION_DELAY: Duration = Duration::from_secs(15);
+
 #[derive(Debug, Clone)]
 struct Crate {
     name: String,
     path: PathBuf,
     local_deps: Vec<String>,
+    /// `package.publish`; `false` keeps the crate off crates.io.
+    publish: bool,
+}
+
+/// A workspace crate left out of the publish plan.
+#[derive(Debug, Clone)]
+struct Skipped {
+    name: String,
+    reason: String,
+}
+
+/// Context of a publish that failed after some crates had reached crates.io.
+///
+/// Those versions can only be yanked, so callers must keep the commit that declares them.
+#[derive(Debug)]
+pub struct PartialPublish {
+    pub published: Vec<String>,
+}
+
+impl fmt::Display for PartialPublish {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "publish failed after {} reached crates.io",
+            self.published.join(", ")
+        )
+    }
+}
+
+/// The `[publish]` table of `xtask.toml`.
+#[derive(Debug, Default)]
+struct PublishConfig {
+    /// Crates never published, even without `publish = false` in their manifest.
+    skip: Vec<String>,
 }
 
 pub fn run_publish_parallel(sh: &Shell, dry_run: bool) -> Result<()> {
@@ -19,8 +64,14 @@ pub fn run_publish_parallel(sh: &Shell, dry_run: bool) -> Result<()> {
     let crates = load_workspace()?;
     println!("📦 Found {} crates in workspace.", crates.len());
 
+    let config = load_config(Path::new(CONFIG_FILE))?;
+    let (crates, skipped) = partition(crates, &config);
+    for skip in &skipped {
+        println!("   [SKIP] {} ({})", skip.name, skip.reason);
+    }
+
     // 2. Build Dependency Graph & Layers
-    let layers = topological_sort(&crates)?;
+    let layers = topological_sort(&crates, &skipped)?;
 
     println!("📊 Computed {} dependency layers.", layers.len());
     for (i, layer) in layers.iter().enumerate() {
@@ -29,24 +80,79 @@ pub fn run_publish_parallel(sh: &Shell, dry_run: bool) -> Result<()> {
     }
 
     // 3. Execute Layers Sequentially
+    let index_delay = if dry_run {
+        Duration::ZERO
+    } else {
+        INDEX_PROPAGATION_DELAY
+    };
+    publish_layers(&layers, dry_run, index_delay, |krate| {
+        publish_crate(dry_run, krate)
+    })?;
+
+    // 4. Make sure what we pushed actually resolves
+    if !dry_run {
+        let names: Vec<String> = layers.iter().flatten().map(|c| c.name.clone()).collect();
+        let version = verify::workspace_version()?;
+        verify::run_verify_publish(
+            &names,
+            &version,
+            std::time::Duration::from_secs(verify::DEFAULT_TIMEOUT_SECS),
+        )
+        .context(PartialPublish { published: names })?;
+    }
+
+    println!("\n✅ Parallel Publish Complete!");
+    Ok(())
+}
+
+/// Runs `publish` on every layer in order, the crates of one layer in parallel, waiting
+/// `index_delay` between layers.
+///
+/// Unless `dry_run`, a failure after any crate went out carries a [`PartialPublish`].
+fn publish_layers(
+    layers: &[Vec<Crate>],
+    dry_run: bool,
+    index_delay: Duration,
+    publish: impl Fn(&Crate) -> Result<()> + Sync,
+) -> Result<()> {
+    let published = Mutex::new(Vec::new());
     for (i, layer) in layers.iter().enumerate() {
         println!("\n▶️  Executing Layer {} ({})", i, layer.len());
 
         // Execute crates in this layer in parallel
-        layer
-            .par_iter()
-            .try_for_each(|krate| -> Result<()> { publish_crate(dry_run, krate) })?;
+        let result = layer.par_iter().try_for_each(|krate| -> Result<()> {
+            publish(krate)?;
+            published.lock().unwrap().push(krate.name.clone());
+            Ok(())
+        });
+        if let Err(e) = result {
+            let published = published.into_inner().unwrap();
+            if dry_run || published.is_empty() {
+                return Err(e);
+            }
+            return Err(e.context(PartialPublish { published }));
+        }
 
-        if !dry_run && i < layers.len() - 1 {
-            println!("⏳ Waiting 15s for crates.io index propagation...");
-            std::thread::sleep(std::time::Duration::from_secs(15));
+        if !index_delay.is_zero() && i < layers.len() - 1 {
+            println!(
+                "⏳ Waiting {}s for crates.io index propagation...",
+                index_delay.as_secs()
+            );
+            std::thread::sleep(index_delay);
         }
     }
-
-    println!("\n✅ Parallel Publish Complete!");
     Ok(())
 }
 
+/// Names of the crates `run_publish_parallel` would publish.
+pub fn publishable_crates() -> Result<Vec<String>> {
+    let config = load_config(Path::new(CONFIG_FILE))?;
+    let (crates, _) = partition(load_workspace()?, &config);
+    let mut names: Vec<String> = crates.into_iter().map(|c| c.name).collect();
+    names.sort();
+    Ok(names)
+}
+
 fn publish_crate(dry_run: bool, krate: &Crate) -> Result<()> {
     // We need a thread-local Shell because Shell is not Sync/Send usually?
     // Actually xshell::Shell is !Sync. We can use std::process or create new Shell.
@@ -135,6 +241,11 @@ fn parse_crate(path: &Path) -> Result<Crate> {
         .context("Missing package name")?
         .to_string();
 
+    let publish = doc["package"]
+        .get("publish")
+        .and_then(|p| p.as_bool())
+        .unwrap_or(true);
+
     let mut local_deps = Vec::new();
 
     // Check dependencies section
@@ -154,10 +265,77 @@ fn parse_crate(path: &Path) -> Result<Crate> {
         name,
         path: path.to_path_buf(),
         local_deps,
+        publish,
     })
 }
 
-fn topological_sort(crates: &[Crate]) -> Result<Vec<Vec<Crate>>> {
+fn load_config(path: &Path) -> Result<PublishConfig> {
+    if !path.exists() {
+        return Ok(PublishConfig::default());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let doc = content
+        .parse::<toml_edit::DocumentMut>()
+        .with_context(|| format!("Failed to parse {:?}", path))?;
+
+    let skip = doc
+        .get("publish")
+        .and_then(|p| p.get("skip"))
+        .and_then(|s| s.as_array())
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|n| n.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(PublishConfig { skip })
+}
+
+/// Splits the workspace into crates to publish and crates to leave alone.
+fn partition(crates: Vec<Crate>, config: &PublishConfig) -> (Vec<Crate>, Vec<Skipped>) {
+    let mut publishable = Vec::new();
+    let mut skipped = Vec::new();
+    for krate in crates {
+        let reason = if !krate.publish {
+            Some("publish = false")
+        } else if config.skip.contains(&krate.name) {
+            Some("listed in xtask.toml publish.skip")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => skipped.push(Skipped {
+                name: krate.name,
+                reason: reason.to_string(),
+            }),
+            None => publishable.push(krate),
+        }
+    }
+    skipped.sort_by(|a, b| a.name.cmp(&b.name));
+    (publishable, skipped)
+}
+
+/// Orders `crates` into layers that only depend on earlier layers.
+///
+/// Skipped crates never block a layer, but a publishable crate depending on one could
+/// never resolve from crates.io, so that is an error.
+fn topological_sort(crates: &[Crate], skipped: &[Skipped]) -> Result<Vec<Vec<Crate>>> {
+    for krate in crates {
+        for dep in &krate.local_deps {
+            if let Some(skip) = skipped.iter().find(|s| &s.name == dep) {
+                anyhow::bail!(
+                    "{} depends on {}, which is not published ({})",
+                    krate.name,
+                    skip.name,
+                    skip.reason
+                );
+            }
+        }
+    }
+
     let mut layers = Vec::new();
     let mut remaining: HashMap<String, Crate> =
         crates.iter().map(|c| (c.name.clone(), c.clone())).collect();
@@ -204,3 +382,170 @@ fn topological_sort(crates: &[Crate]) -> Result<Vec<Vec<Crate>>> {
 
     Ok(layers)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn krate(name: &str, deps: &[&str]) -> Crate {
+        Crate {
+            name: name.to_string(),
+            path: PathBuf::from("crates").join(name),
+            local_deps: deps.iter().map(|d| d.to_string()).collect(),
+            publish: true,
+        }
+    }
+
+    fn names(layers: &[Vec<Crate>]) -> Vec<Vec<&str>> {
+        layers
+            .iter()
+            .map(|layer| layer.iter().map(|c| c.name.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_layers_follow_dependencies() {
+        let crates = vec![
+            krate("praborrow", &["praborrow-core", "praborrow-lease", "serde"]),
+            krate("praborrow-lease", &["praborrow-core", "tokio"]),
+            krate("praborrow-core", &["serde"]),
+            krate("praborrow-macros", &["syn"]),
+        ];
+        let layers = topological_sort(&crates, &[]).unwrap();
+        assert_eq!(
+            names(&layers),
+            vec![
+                vec!["praborrow-core", "praborrow-macros"],
+                vec!["praborrow-lease"],
+                vec!["praborrow"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skipped_members_are_left_out() {
+        let mut dart = krate("praborrow-dart", &["praborrow-core"]);
+        dart.publish = false;
+        let crates = vec![
+            krate("praborrow-core", &[]),
+            krate("praborrow-wasm", &["praborrow-core"]),
+            dart,
+            krate("praborrow", &["praborrow-core"]),
+        ];
+        let config = PublishConfig {
+            skip: vec!["praborrow-wasm".to_string()],
+        };
+
+        let (crates, skipped) = partition(crates, &config);
+        let reasons: Vec<_> = skipped
+            .iter()
+            .map(|s| (s.name.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("praborrow-dart", "publish = false"),
+                ("praborrow-wasm", "listed in xtask.toml publish.skip"),
+            ]
+        );
+
+        let layers = topological_sort(&crates, &skipped).unwrap();
+        assert_eq!(
+            names(&layers),
+            vec![vec!["praborrow-core"], vec!["praborrow"]]
+        );
+    }
+
+    #[test]
+    fn test_depending_on_skipped_crate_is_an_error() {
+        let crates = vec![
+            krate("praborrow-core", &[]),
+            krate("praborrow", &["praborrow-core", "praborrow-wasm"]),
+        ];
+        let skipped = vec![Skipped {
+            name: "praborrow-wasm".to_string(),
+            reason: "publish = false".to_string(),
+        }];
+        let err = topological_sort(&crates, &skipped).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "praborrow depends on praborrow-wasm, which is not published (publish = false)"
+        );
+    }
+
+    #[test]
+    fn test_failed_layer_reports_what_was_published() {
+        let crates = vec![
+            krate("praborrow-lease", &["praborrow-core"]),
+            krate("praborrow-core", &[]),
+            krate("praborrow-macros", &[]),
+        ];
+        let layers = topological_sort(&crates, &[]).unwrap();
+        let fail_on = |name: &'static str| {
+            move |krate: &Crate| {
+                if krate.name == name {
+                    anyhow::bail!("upload of {} rejected", name);
+                }
+                Ok(())
+            }
+        };
+
+        let err =
+            publish_layers(&layers, false, Duration::ZERO, fail_on("praborrow-lease")).unwrap_err();
+        let mut published = err
+            .downcast_ref::<PartialPublish>()
+            .unwrap()
+            .published
+            .clone();
+        published.sort();
+        assert_eq!(published, ["praborrow-core", "praborrow-macros"]);
+        assert!(format!("{:#}", err).ends_with("upload of praborrow-lease rejected"));
+
+        // Nothing went out, or nothing would have
+        let err = publish_layers(&layers, false, Duration::ZERO, |_: &Crate| {
+            anyhow::bail!("offline")
+        })
+        .unwrap_err();
+        assert!(err.downcast_ref::<PartialPublish>().is_none());
+        let err =
+            publish_layers(&layers, true, Duration::ZERO, fail_on("praborrow-lease")).unwrap_err();
+        assert!(err.downcast_ref::<PartialPublish>().is_none());
+    }
+
+    #[test]
+    fn test_cycle_is_an_error() {
+        let crates = vec![krate("a", &["b"]), krate("b", &["a"])];
+        assert!(topological_sort(&crates, &[]).is_err());
+    }
+
+    #[test]
+    fn test_load_config_reads_skip_list() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        assert!(load_config(&path).unwrap().skip.is_empty());
+
+        fs::write(
+            &path,
+            "[publish]\nskip = [\"praborrow-dart\", \"praborrow-wasm\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            load_config(&path).unwrap().skip,
+            vec!["praborrow-dart", "praborrow-wasm"]
+        );
+    }
+
+    #[test]
+    fn test_parse_crate_reads_publish_flag() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"praborrow-dart\"\npublish = false\n\n[dependencies]\npraborrow-core = { workspace = true }\n",
+        )
+        .unwrap();
+        let krate = parse_crate(dir.path()).unwrap();
+        assert_eq!(krate.name, "praborrow-dart");
+        assert!(!krate.publish);
+        assert_eq!(krate.local_deps, vec!["praborrow-core"]);
+    }
+}
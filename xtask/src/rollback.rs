@@ -0,0 +1,306 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+use xshell::{cmd, Shell};
+
+/// Contents of one file before the release touched it; `None` if it did not exist.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    path: PathBuf,
+    contents: Option<Vec<u8>>,
+}
+
+/// What [`RollbackPlan::rollback`] undid.
+#[derive(Debug, Default)]
+pub struct RollbackReport {
+    /// Repositories whose release commit was dropped, as `(repo, restored HEAD)`.
+    pub reset: Vec<(PathBuf, String)>,
+    pub restored: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl RollbackReport {
+    pub fn is_empty(&self) -> bool {
+        self.reset.is_empty() && self.restored.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Everything a release may modify, captured before the version bump.
+///
+/// The release commits locally and only pushes once publishing succeeded, so rolling
+/// back means moving each repository back to its recorded HEAD and rewriting every
+/// recorded file byte-for-byte.
+#[derive(Debug)]
+pub struct RollbackPlan {
+    root: PathBuf,
+    files: Vec<Snapshot>,
+    /// Root repository (empty path) and every checked out submodule.
+    heads: Vec<(PathBuf, String)>,
+}
+
+impl RollbackPlan {
+    /// Records `files` (relative to `root`) and the HEAD of every repository.
+    pub fn capture(root: &Path, files: Vec<PathBuf>) -> Result<Self> {
+        let sh = Shell::new()?;
+        sh.change_dir(root);
+
+        let mut repos = vec![PathBuf::new()];
+        repos.extend(
+            crate::changelog::submodule_paths(&sh)
+                .into_iter()
+                .map(PathBuf::from)
+                .filter(|path| root.join(path).join(".git").exists()),
+        );
+        let mut heads = Vec::new();
+        for repo in repos {
+            let _guard = sh.push_dir(&repo);
+            let head = cmd!(sh, "git rev-parse HEAD").quiet().read()?;
+            heads.push((repo, head));
+        }
+
+        let files = files
+            .into_iter()
+            .map(|path| Snapshot {
+                contents: fs::read(root.join(&path)).ok(),
+                path,
+            })
+            .collect();
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            files,
+            heads,
+        })
+    }
+
+    /// Drops unpushed release commits and restores every recorded file.
+    pub fn rollback(&self) -> Result<RollbackReport> {
+        let sh = Shell::new()?;
+        sh.change_dir(&self.root);
+        let mut report = RollbackReport::default();
+
+        // 1. Commits made by the release; submodules first so the root sees their old HEADs
+        for (repo, head) in self.heads.iter().rev() {
+            let _guard = sh.push_dir(repo);
+            let current = cmd!(sh, "git rev-parse HEAD").quiet().read()?;
+            if &current != head {
+                cmd!(sh, "git reset -q --soft {head}").quiet().run()?;
+                report.reset.push((repo.clone(), head.clone()));
+            }
+        }
+
+        // 2. File contents
+        for snapshot in &self.files {
+            let path = self.root.join(&snapshot.path);
+            let current = fs::read(&path).ok();
+            if current == snapshot.contents {
+                continue;
+            }
+            match &snapshot.contents {
+                Some(contents) => {
+                    fs::write(&path, contents)
+                        .with_context(|| format!("Failed to restore {:?}", path))?;
+                    report.restored.push(snapshot.path.clone());
+                }
+                None => {
+                    fs::remove_file(&path)
+                        .with_context(|| format!("Failed to remove {:?}", path))?;
+                    report.removed.push(snapshot.path.clone());
+                }
+            }
+        }
+
+        // 3. A soft reset leaves the release changes staged
+        for (repo, _) in &report.reset {
+            let _guard = sh.push_dir(repo);
+            cmd!(sh, "git reset -q").quiet().run()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Fails with the leftover paths unless `git status --porcelain` is empty.
+    pub fn verify_clean(&self) -> Result<()> {
+        let sh = Shell::new()?;
+        sh.change_dir(&self.root);
+        let status = cmd!(sh, "git status --porcelain").quiet().read()?;
+        if !status.trim().is_empty() {
+            anyhow::bail!("Working tree still dirty after rollback:\n{}", status);
+        }
+        Ok(())
+    }
+}
+
+/// Files a release writes: manifests, the lockfile and changelogs of the workspace and
+/// every member.
+pub fn release_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let content = fs::read_to_string(root.join("Cargo.toml"))?;
+    let doc = content.parse::<DocumentMut>()?;
+    let members: Vec<String> = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut files = vec![
+        PathBuf::from("Cargo.toml"),
+        PathBuf::from("Cargo.lock"),
+        PathBuf::from("CHANGELOG.md"),
+    ];
+    for member in members {
+        let dir = Path::new(&member);
+        if !root.join(dir).exists() {
+            continue;
+        }
+        files.push(dir.join("Cargo.toml"));
+        files.push(dir.join("CHANGELOG.md"));
+    }
+    // `changelog` writes into every directory under crates/, members or not
+    if let Ok(entries) = fs::read_dir(root.join("crates")) {
+        for entry in entries.flatten() {
+            let changelog = Path::new("crates")
+                .join(entry.file_name())
+                .join("CHANGELOG.md");
+            if entry.path().is_dir() && !files.contains(&changelog) {
+                files.push(changelog);
+            }
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ROOT: &str = "[workspace]\nmembers = [\"crates/praborrow-core\", \"crates/praborrow\"]\n\n[workspace.package]\nversion = \"1.2.3\"\n";
+    const MEMBER: &str = "[package]\nversion.workspace = true\n";
+
+    /// A committed workspace with two members.
+    fn repo() -> (TempDir, Shell) {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("Cargo.toml"), ROOT).unwrap();
+        for member in ["crates/praborrow-core", "crates/praborrow"] {
+            fs::create_dir_all(root.join(member)).unwrap();
+            fs::write(root.join(member).join("Cargo.toml"), MEMBER).unwrap();
+        }
+
+        let sh = Shell::new().unwrap();
+        sh.change_dir(root);
+        cmd!(sh, "git init -q").run().unwrap();
+        commit(&sh, "initial");
+        (dir, sh)
+    }
+
+    fn commit(sh: &Shell, message: &str) {
+        cmd!(sh, "git add -A").run().unwrap();
+        cmd!(
+            sh,
+            "git -c user.name=xtask -c user.email=xtask@example.com -c commit.gpgsign=false commit -q -m {message}"
+        )
+        .run()
+        .unwrap();
+    }
+
+    fn head(sh: &Shell) -> String {
+        cmd!(sh, "git rev-parse HEAD").read().unwrap()
+    }
+
+    /// What a release does before building: bump the root and one member, add a changelog.
+    fn bump(root: &Path) {
+        fs::write(root.join("Cargo.toml"), ROOT.replace("1.2.3", "1.2.4")).unwrap();
+        fs::write(
+            root.join("crates/praborrow-core/Cargo.toml"),
+            format!("{}description = \"bumped\"\n", MEMBER),
+        )
+        .unwrap();
+        fs::write(root.join("CHANGELOG.md"), "# Changelog\n\n## [1.2.4]\n").unwrap();
+    }
+
+    #[test]
+    fn test_release_files() {
+        let (dir, _sh) = repo();
+        fs::create_dir_all(dir.path().join("crates/praborrow-wasm")).unwrap();
+        let files = release_files(dir.path()).unwrap();
+        let expected: Vec<PathBuf> = [
+            "Cargo.toml",
+            "Cargo.lock",
+            "CHANGELOG.md",
+            "crates/praborrow-core/Cargo.toml",
+            "crates/praborrow-core/CHANGELOG.md",
+            "crates/praborrow/Cargo.toml",
+            "crates/praborrow/CHANGELOG.md",
+            "crates/praborrow-wasm/CHANGELOG.md",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(files, expected);
+    }
+
+    #[test]
+    fn test_partial_bump_is_restored() {
+        let (dir, _sh) = repo();
+        let plan = RollbackPlan::capture(dir.path(), release_files(dir.path()).unwrap()).unwrap();
+        bump(dir.path());
+
+        let report = plan.rollback().unwrap();
+        assert!(report.reset.is_empty());
+        assert_eq!(
+            report.restored,
+            vec![
+                PathBuf::from("Cargo.toml"),
+                PathBuf::from("crates/praborrow-core/Cargo.toml")
+            ]
+        );
+        assert_eq!(report.removed, vec![PathBuf::from("CHANGELOG.md")]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+            ROOT
+        );
+        plan.verify_clean().unwrap();
+    }
+
+    #[test]
+    fn test_publish_failure_drops_release_commit() {
+        let (dir, sh) = repo();
+        let before = head(&sh);
+        let plan = RollbackPlan::capture(dir.path(), release_files(dir.path()).unwrap()).unwrap();
+        bump(dir.path());
+        commit(&sh, "chore: release 1.2.4");
+        assert_ne!(head(&sh), before);
+
+        let report = plan.rollback().unwrap();
+        assert_eq!(report.reset, vec![(PathBuf::new(), before.clone())]);
+        assert_eq!(report.restored.len(), 2);
+        assert_eq!(report.removed, vec![PathBuf::from("CHANGELOG.md")]);
+        assert_eq!(head(&sh), before);
+        plan.verify_clean().unwrap();
+    }
+
+    #[test]
+    fn test_untouched_tree_reports_nothing() {
+        let (dir, _sh) = repo();
+        let plan = RollbackPlan::capture(dir.path(), release_files(dir.path()).unwrap()).unwrap();
+        assert!(plan.rollback().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_verify_clean_lists_leftovers() {
+        let (dir, _sh) = repo();
+        let plan = RollbackPlan::capture(dir.path(), vec![PathBuf::from("Cargo.toml")]).unwrap();
+        fs::write(dir.path().join("stray.txt"), "not part of the release").unwrap();
+        plan.rollback().unwrap();
+        let err = plan.verify_clean().unwrap_err();
+        assert!(err.to_string().contains("stray.txt"));
+    }
+}
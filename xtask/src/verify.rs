@@ -0,0 +1,280 @@
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use xshell::{cmd, Shell};
+
+/// The crate users depend on; checked by building a scratch project against it.
+pub const FACADE: &str = "praborrow";
+
+/// How long to wait for every crate to appear in the index.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 600;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+const SPARSE_INDEX: &str = "https://index.crates.io";
+
+/// Looks up published versions; injected so polling can run against a stub.
+pub trait IndexClient {
+    fn has_version(&self, name: &str, version: &str) -> Result<bool>;
+}
+
+/// The crates.io sparse index, fetched with `curl`.
+pub struct SparseIndex {
+    pub base_url: String,
+}
+
+impl Default for SparseIndex {
+    fn default() -> Self {
+        Self {
+            base_url: SPARSE_INDEX.to_string(),
+        }
+    }
+}
+
+impl IndexClient for SparseIndex {
+    fn has_version(&self, name: &str, version: &str) -> Result<bool> {
+        let sh = Shell::new()?;
+        let url = format!("{}/{}", self.base_url, index_path(name));
+        // A 404 just means the crate has not been indexed yet
+        let Ok(body) = cmd!(sh, "curl -sfL {url}").quiet().read() else {
+            return Ok(false);
+        };
+        Ok(index_has_version(&body, version))
+    }
+}
+
+/// Path of a crate's file in the registry index, e.g. `pr/ab/praborrow`.
+pub fn index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+/// Whether an index file (one JSON object per published version) lists `version`.
+pub fn index_has_version(body: &str, version: &str) -> bool {
+    let needle = format!("\"vers\":\"{}\"", version);
+    body.lines().any(|line| line.contains(&needle))
+}
+
+/// Polls `client` until every crate resolves at `version` or `timeout` elapses.
+pub fn wait_for_versions(
+    client: &dyn IndexClient,
+    crates: &[String],
+    version: &str,
+    timeout: Duration,
+    interval: Duration,
+) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    let mut pending: Vec<&String> = crates.iter().collect();
+
+    loop {
+        let mut still_pending = Vec::new();
+        for name in pending {
+            if client.has_version(name, version)? {
+                println!("   ✅ {}@{} is indexed", name, version);
+            } else {
+                still_pending.push(name);
+            }
+        }
+        pending = still_pending;
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+        if Instant::now() + interval > deadline {
+            let missing: Vec<String> = pending
+                .iter()
+                .map(|name| format!("{}@{}", name, version))
+                .collect();
+            anyhow::bail!(
+                "Not indexed after {}s: {}",
+                timeout.as_secs(),
+                missing.join(", ")
+            );
+        }
+        println!(
+            "   ⏳ Waiting for {} crate(s) to be indexed...",
+            pending.len()
+        );
+        std::thread::sleep(interval);
+    }
+}
+
+/// Writes a minimal binary crate depending on exactly `facade@version`.
+///
+/// The empty `[workspace]` table keeps cargo from attaching the project to a parent
+/// workspace when the scratch dir lives inside one.
+pub fn scaffold_project(dir: &Path, facade: &str, version: &str) -> Result<()> {
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"verify-{facade}\"\nversion = \"0.0.0\"\nedition = \"2021\"\npublish = false\n\n[workspace]\n\n[dependencies]\n{facade} = \"={version}\"\n"
+        ),
+    )?;
+    fs::write(dir.join("src/main.rs"), "fn main() {}\n")?;
+    Ok(())
+}
+
+pub fn run_verify_publish(crates: &[String], version: &str, timeout: Duration) -> Result<()> {
+    println!("{}", "🔎 Verifying published versions...".cyan().bold());
+
+    // 1. Index
+    wait_for_versions(
+        &SparseIndex::default(),
+        crates,
+        version,
+        timeout,
+        POLL_INTERVAL,
+    )?;
+
+    // 2. Fresh project against the facade
+    let dir = scratch_dir(version);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    scaffold_project(&dir, FACADE, version)?;
+    println!("   Checking {}@{} in {}", FACADE, version, dir.display());
+
+    let sh = Shell::new()?;
+    let _guard = sh.push_dir(&dir);
+    if let Err(e) = cmd!(sh, "cargo check").run() {
+        println!(
+            "{}",
+            format!("❌ {}@{} does not build from crates.io", FACADE, version).red()
+        );
+        return Err(e.into());
+    }
+    fs::remove_dir_all(&dir).ok();
+
+    println!("{}", "✅ Published versions resolve".green());
+    Ok(())
+}
+
+fn scratch_dir(version: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("praborrow-verify-{}", version))
+}
+
+/// `workspace.package.version` from the root manifest.
+pub fn workspace_version() -> Result<String> {
+    let content = fs::read_to_string("Cargo.toml")?;
+    let doc = content.parse::<toml_edit::DocumentMut>()?;
+    doc.get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .context("Missing workspace.package.version")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// Index stub where each crate appears after a number of lookups.
+    struct StubIndex {
+        appears_after: HashMap<&'static str, usize>,
+        lookups: RefCell<Vec<String>>,
+    }
+
+    impl StubIndex {
+        fn new(appears_after: &[(&'static str, usize)]) -> Self {
+            Self {
+                appears_after: appears_after.iter().copied().collect(),
+                lookups: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl IndexClient for StubIndex {
+        fn has_version(&self, name: &str, version: &str) -> Result<bool> {
+            assert_eq!(version, "1.2.3");
+            let mut lookups = self.lookups.borrow_mut();
+            lookups.push(name.to_string());
+            let seen = lookups.iter().filter(|n| n.as_str() == name).count();
+            Ok(self
+                .appears_after
+                .get(name)
+                .is_some_and(|&after| seen > after))
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_index_path() {
+        assert_eq!(index_path("a"), "1/a");
+        assert_eq!(index_path("ab"), "2/ab");
+        assert_eq!(index_path("abc"), "3/a/abc");
+        assert_eq!(index_path("Praborrow-Core"), "pr/ab/praborrow-core");
+    }
+
+    #[test]
+    fn test_index_has_version() {
+        let body = concat!(
+            r#"{"name":"praborrow","vers":"1.2.2","deps":[]}"#,
+            "\n",
+            r#"{"name":"praborrow","vers":"1.2.3","deps":[]}"#,
+            "\n"
+        );
+        assert!(index_has_version(body, "1.2.3"));
+        assert!(!index_has_version(body, "1.2.30"));
+        assert!(!index_has_version("", "1.2.3"));
+    }
+
+    #[test]
+    fn test_waits_until_every_crate_is_indexed() {
+        let index = StubIndex::new(&[("praborrow-core", 0), ("praborrow", 2)]);
+        wait_for_versions(
+            &index,
+            &names(&["praborrow-core", "praborrow"]),
+            "1.2.3",
+            Duration::from_secs(1),
+            Duration::ZERO,
+        )
+        .unwrap();
+        // Indexed crates are not polled again
+        assert_eq!(
+            *index.lookups.borrow(),
+            names(&["praborrow-core", "praborrow", "praborrow", "praborrow"])
+        );
+    }
+
+    #[test]
+    fn test_timeout_names_missing_crates() {
+        let index = StubIndex::new(&[("praborrow-core", 0)]);
+        let err = wait_for_versions(
+            &index,
+            &names(&["praborrow-core", "praborrow-lease", "praborrow"]),
+            "1.2.3",
+            Duration::ZERO,
+            Duration::from_millis(1),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Not indexed after 0s: praborrow-lease@1.2.3, praborrow@1.2.3"
+        );
+    }
+
+    #[test]
+    fn test_scaffold_project_pins_facade() {
+        let dir = tempfile::TempDir::new().unwrap();
+        scaffold_project(dir.path(), FACADE, "1.2.3").unwrap();
+        let manifest = fs::read_to_string(dir.path().join("Cargo.toml")).unwrap();
+        let doc = manifest.parse::<toml_edit::DocumentMut>().unwrap();
+        assert_eq!(doc["dependencies"]["praborrow"].as_str(), Some("=1.2.3"));
+        assert!(doc.get("workspace").is_some());
+        assert!(dir.path().join("src/main.rs").exists());
+    }
+}
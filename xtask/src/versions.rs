@@ -0,0 +1,432 @@
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+/// Prefix shared by every internal crate.
+const INTERNAL_PREFIX: &str = "praborrow";
+
+/// The facade crate whose features must map onto its optional dependencies.
+const FACADE_DIR: &str = "crates/praborrow";
+
+const DEP_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub file: PathBuf,
+    /// Dotted TOML path, e.g. `workspace.dependencies.praborrow-core.version`.
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.file.display(), self.key, self.message)
+    }
+}
+
+/// Result of scanning a workspace.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub version: String,
+    pub checked: Vec<PathBuf>,
+    /// Members without a manifest, e.g. submodules that are not checked out.
+    pub missing: Vec<PathBuf>,
+    pub violations: Vec<Violation>,
+}
+
+pub fn run_check_versions() -> Result<()> {
+    println!(
+        "{}",
+        "🔢 Checking internal version consistency...".cyan().bold()
+    );
+
+    let report = check_workspace(Path::new("."))?;
+    println!("   Workspace version: {}", report.version.cyan());
+    println!("   Checked {} manifests", report.checked.len());
+    for path in &report.missing {
+        println!(
+            "{}",
+            format!(
+                "   ⚠️  {} has no Cargo.toml (submodule not checked out?)",
+                path.display()
+            )
+            .yellow()
+        );
+    }
+
+    if report.violations.is_empty() {
+        println!("{}", "✅ Internal versions consistent".green());
+        return Ok(());
+    }
+
+    for violation in &report.violations {
+        println!("   {} {}", "❌".red(), violation);
+    }
+    anyhow::bail!("{} version violation(s) found", report.violations.len());
+}
+
+/// Scans the workspace rooted at `root` and collects every violation.
+pub fn check_workspace(root: &Path) -> Result<Report> {
+    let root_manifest = root.join("Cargo.toml");
+    let doc = read_manifest(&root_manifest)?;
+
+    let version = doc
+        .get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .context("Missing workspace.package.version")?
+        .to_string();
+
+    let mut report = Report {
+        version: version.clone(),
+        checked: vec![root_manifest.clone()],
+        ..Default::default()
+    };
+
+    // 1. Versions pinned in [workspace.dependencies]
+    if let Some(deps) = doc
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table_like())
+    {
+        for (name, item) in deps.iter() {
+            check_dependency(
+                &root_manifest,
+                &format!("workspace.dependencies.{}", name),
+                name,
+                item,
+                &version,
+                &mut report.violations,
+            );
+        }
+    }
+
+    // 2. Every member manifest
+    let members: Vec<String> = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for member in members {
+        let manifest = root.join(&member).join("Cargo.toml");
+        if !manifest.exists() {
+            report.missing.push(root.join(&member));
+            continue;
+        }
+        let member_doc = read_manifest(&manifest)?;
+        check_member(&manifest, &member_doc, &version, &mut report.violations);
+        if Path::new(&member) == Path::new(FACADE_DIR) {
+            check_features(&manifest, &member_doc, &mut report.violations);
+        }
+        report.checked.push(manifest);
+    }
+
+    Ok(report)
+}
+
+fn read_manifest(path: &Path) -> Result<DocumentMut> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {:?}", path))
+}
+
+/// Checks that a published member inherits the workspace version and that internal
+/// dependencies declared with an explicit version agree with it.
+fn check_member(manifest: &Path, doc: &DocumentMut, version: &str, out: &mut Vec<Violation>) {
+    let package = doc.get("package");
+    let publishable = package
+        .and_then(|p| p.get("publish"))
+        .and_then(|p| p.as_bool())
+        .unwrap_or(true);
+
+    // Internal tooling such as xtask is never released, so it may keep its own version
+    if publishable {
+        let inherits = package
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.get("workspace"))
+            .and_then(|w| w.as_bool())
+            .unwrap_or(false);
+        if !inherits {
+            out.push(Violation {
+                file: manifest.to_path_buf(),
+                key: "package.version".to_string(),
+                message: "must be `version.workspace = true`".to_string(),
+            });
+        }
+    }
+
+    for table in DEP_TABLES {
+        if let Some(deps) = doc.get(table).and_then(|d| d.as_table_like()) {
+            for (name, item) in deps.iter() {
+                check_dependency(
+                    manifest,
+                    &format!("{}.{}", table, name),
+                    name,
+                    item,
+                    version,
+                    out,
+                );
+            }
+        }
+    }
+}
+
+fn check_dependency(
+    manifest: &Path,
+    key: &str,
+    name: &str,
+    item: &Item,
+    version: &str,
+    out: &mut Vec<Violation>,
+) {
+    if !name.starts_with(INTERNAL_PREFIX) {
+        return;
+    }
+    // `praborrow-core = "1.2.3"` or `{ version = "1.2.3", ... }`; `workspace = true`
+    // entries carry no version and are covered by the root manifest
+    let requirement = match item.as_str() {
+        Some(req) => Some((req, key.to_string())),
+        None => item
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(|req| (req, format!("{}.version", key))),
+    };
+    if let Some((requirement, key)) = requirement {
+        if !requirement_matches(requirement, version) {
+            out.push(Violation {
+                file: manifest.to_path_buf(),
+                key,
+                message: format!(
+                    "requires `{}` but the workspace version is {}",
+                    requirement, version
+                ),
+            });
+        }
+    }
+}
+
+/// Whether a requirement such as `1.2.3`, `^1.2.3` or `=1.2.3` pins exactly `version`.
+fn requirement_matches(requirement: &str, version: &str) -> bool {
+    requirement
+        .trim()
+        .trim_start_matches(|c| c == '=' || c == '^')
+        .trim()
+        == version
+}
+
+/// Checks that every feature of the facade references an optional dependency or
+/// another feature.
+fn check_features(manifest: &Path, doc: &DocumentMut, out: &mut Vec<Violation>) {
+    let Some(features) = doc.get("features").and_then(|f| f.as_table_like()) else {
+        return;
+    };
+    let feature_names: HashSet<&str> = features.iter().map(|(name, _)| name).collect();
+
+    let mut dependencies = HashSet::new();
+    let mut optional = HashSet::new();
+    if let Some(deps) = doc.get("dependencies").and_then(|d| d.as_table_like()) {
+        for (name, item) in deps.iter() {
+            dependencies.insert(name);
+            if item.get("optional").and_then(|o| o.as_bool()) == Some(true) {
+                optional.insert(name);
+            }
+        }
+    }
+
+    for (feature, item) in features.iter() {
+        let Some(entries) = item.as_array() else {
+            continue;
+        };
+        for entry in entries.iter().filter_map(|e| e.as_str()) {
+            let problem = if let Some(dep) = entry.strip_prefix("dep:") {
+                (!optional.contains(dep))
+                    .then(|| format!("`{}` is not an optional dependency", dep))
+            } else if let Some((dep, _)) = entry.split_once('/') {
+                let (dep, weak) = match dep.strip_suffix('?') {
+                    Some(dep) => (dep, true),
+                    None => (dep, false),
+                };
+                if weak && !optional.contains(dep) {
+                    Some(format!("`{}` is not an optional dependency", dep))
+                } else if !dependencies.contains(dep) {
+                    Some(format!("`{}` is not a dependency", dep))
+                } else {
+                    None
+                }
+            } else {
+                (!feature_names.contains(entry) && !optional.contains(entry)).then(|| {
+                    format!(
+                        "`{}` is neither a feature nor an optional dependency",
+                        entry
+                    )
+                })
+            };
+            if let Some(message) = problem {
+                out.push(Violation {
+                    file: manifest.to_path_buf(),
+                    key: format!("features.{}", feature),
+                    message,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ROOT: &str = r#"[workspace]
+members = ["crates/praborrow-core", "crates/praborrow", "crates/praborrow-sidl"]
+
+[workspace.package]
+version = "1.2.3"
+
+[workspace.dependencies]
+praborrow-core = { path = "crates/praborrow-core", version = "1.2.3" }
+serde = "1.0"
+"#;
+
+    const CORE: &str = r#"[package]
+name = "praborrow-core"
+version.workspace = true
+"#;
+
+    const FACADE: &str = r#"[package]
+name = "praborrow"
+version.workspace = true
+
+[dependencies]
+praborrow-core = { workspace = true }
+praborrow-prover = { version = "1.2.3", optional = true }
+
+[features]
+default = ["prover"]
+prover = ["dep:praborrow-prover"]
+"#;
+
+    fn workspace(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn keys(report: &Report) -> Vec<&str> {
+        report.violations.iter().map(|v| v.key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_consistent_workspace() {
+        let dir = workspace(&[
+            ("Cargo.toml", ROOT),
+            ("crates/praborrow-core/Cargo.toml", CORE),
+            ("crates/praborrow/Cargo.toml", FACADE),
+        ]);
+        let report = check_workspace(dir.path()).unwrap();
+        assert_eq!(report.version, "1.2.3");
+        assert!(report.violations.is_empty(), "{:?}", report.violations);
+        // praborrow-sidl is not checked out
+        assert_eq!(report.checked.len(), 3);
+        assert_eq!(
+            report.missing,
+            vec![dir.path().join("crates/praborrow-sidl")]
+        );
+    }
+
+    #[test]
+    fn test_reports_every_mismatch_with_key_path() {
+        let root = ROOT.replace(r#"version = "1.2.3" }"#, r#"version = "=1.2.2" }"#);
+        let core = r#"[package]
+name = "praborrow-core"
+version = "1.2.3"
+
+[dev-dependencies]
+praborrow-macros = "1.2.0"
+"#;
+        let dir = workspace(&[
+            ("Cargo.toml", &root),
+            ("crates/praborrow-core/Cargo.toml", core),
+            ("crates/praborrow/Cargo.toml", FACADE),
+        ]);
+        let report = check_workspace(dir.path()).unwrap();
+        assert_eq!(
+            keys(&report),
+            vec![
+                "workspace.dependencies.praborrow-core.version",
+                "package.version",
+                "dev-dependencies.praborrow-macros",
+            ]
+        );
+        assert_eq!(report.violations[0].file, dir.path().join("Cargo.toml"));
+        assert_eq!(
+            report.violations[1].file,
+            dir.path().join("crates/praborrow-core/Cargo.toml")
+        );
+    }
+
+    #[test]
+    fn test_unpublished_member_keeps_own_version() {
+        let dir = workspace(&[
+            (
+                "Cargo.toml",
+                &ROOT.replace("crates/praborrow-sidl", "xtask"),
+            ),
+            ("crates/praborrow-core/Cargo.toml", CORE),
+            ("crates/praborrow/Cargo.toml", FACADE),
+            (
+                "xtask/Cargo.toml",
+                "[package]\nname = \"xtask\"\nversion = \"0.1.0\"\npublish = false\n",
+            ),
+        ]);
+        assert!(check_workspace(dir.path()).unwrap().violations.is_empty());
+    }
+
+    #[test]
+    fn test_facade_features_must_reference_optional_deps() {
+        let facade = format!(
+            "{}tls = [\"dep:praborrow-core\"]\nserde = [\"praborrow-serde/serde\"]\nfull = [\"prover\", \"missing\"]\n",
+            FACADE
+        );
+        let dir = workspace(&[
+            ("Cargo.toml", ROOT),
+            ("crates/praborrow-core/Cargo.toml", CORE),
+            ("crates/praborrow/Cargo.toml", &facade),
+        ]);
+        let report = check_workspace(dir.path()).unwrap();
+        let messages: Vec<String> = report.violations.iter().map(|v| v.to_string()).collect();
+        assert_eq!(messages.len(), 3, "{:#?}", messages);
+        assert!(
+            messages[0].ends_with("features.tls: `praborrow-core` is not an optional dependency")
+        );
+        assert!(messages[1].ends_with("features.serde: `praborrow-serde` is not a dependency"));
+        assert!(messages[2]
+            .ends_with("features.full: `missing` is neither a feature nor an optional dependency"));
+    }
+
+    #[test]
+    fn test_requirement_matches() {
+        assert!(requirement_matches("1.2.3", "1.2.3"));
+        assert!(requirement_matches("=1.2.3", "1.2.3"));
+        assert!(requirement_matches("^1.2.3", "1.2.3"));
+        assert!(!requirement_matches("1.2", "1.2.3"));
+        assert!(!requirement_matches("=1.2.2", "1.2.3"));
+    }
+}